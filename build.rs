@@ -0,0 +1,14 @@
+use std::fs;
+
+//advent_of_code_setup! generates a test for each file of inputs/{year}/examples, but cargo only tracks the files the macro includes
+//rerun when an examples directory changes so that a new example gets its test without touching the sources
+//the puzzle inputs are not watched, the examples directory of a new year is watched once this script runs again (cargo clean or a change of build.rs)
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    for entry in fs::read_dir("inputs").into_iter().flatten().flatten() {
+        let examples = entry.path().join("examples");
+        if examples.is_dir() {
+            println!("cargo:rerun-if-changed={}", examples.display());
+        }
+    }
+}
@@ -1,51 +1,287 @@
 use proc_macro::TokenStream;
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::fs;
+use std::ops::RangeInclusive;
 use quote::quote;
-use syn::{Ident, Index};
+use syn::{parenthesized, Expr, ExprLit, Ident, Index, Item, Lit, LitInt, LitStr, RangeLimits, Token, Visibility};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
 use proc_macro2::Span;
-use utils::{FIRST_YEAR, get_advent_year_month_day, setup_functions, setup_inputs_and_outputs};
+use utils::{DEFAULT_TEMPLATE, FIRST_YEAR, advent_days, get_advent_year_month_day, setup_functions, setup_inputs_and_outputs, update_mod_file};
+
+///arguments of advent_of_code_setup!(), see the macro for the syntax
+struct Setup {
+    years: RangeInclusive<u32>,
+    days: Vec<(RangeInclusive<u32>, u32)>,
+    template: String,
+}
+
+impl Setup {
+    fn days(&self, year: u32) -> u32 {
+        self.days.iter().rev().find(|(years, _)| years.contains(&year)).map_or_else(|| advent_days(year), |(_, days)| *days)
+    }
+}
+
+fn year_literal(expr: &Expr) -> syn::Result<u32> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(year), .. }) => year.base10_parse(),
+        e => Err(syn::Error::new(e.span(), "expected a year"))
+    }
+}
+
+///a single year (2015) or a range of years (2015..=2024, 2015..2025), an open range ends at the current event
+fn year_range(expr: &Expr) -> syn::Result<RangeInclusive<u32>> {
+    if let Expr::Range(range) = expr {
+        let start = range.start.as_ref().map_or(Ok(FIRST_YEAR), |s| year_literal(s))?;
+        let end = match (&range.end, range.limits) {
+            (Some(e), RangeLimits::Closed(_)) => year_literal(e)?,
+            (Some(e), RangeLimits::HalfOpen(_)) => year_literal(e)? - 1,
+            (None, _) => get_advent_year_month_day().map_or(start, |t| t.0),
+        };
+        Ok(start..=end)
+    } else {
+        year_literal(expr).map(|year| year..=year)
+    }
+}
+
+impl Parse for Setup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut setup = Setup {
+            years: FIRST_YEAR..=get_advent_year_month_day().map_or(0, |t| t.0),
+            days: Vec::new(),
+            template: DEFAULT_TEMPLATE.to_string(),
+        };
+        if let Ok(template) = fs::read_to_string("templates/day.rs.tpl") {
+            setup.template = template;
+        }
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key == "years" {
+                input.parse::<Token![=]>()?;
+                setup.years = year_range(&input.parse()?)?;
+            } else if key == "days" {
+                let years;
+                parenthesized!(years in input);
+                let years = year_range(&years.parse()?)?;
+                input.parse::<Token![=]>()?;
+                let days = input.parse::<LitInt>()?;
+                let count = days.base10_parse()?;
+                if !(1..=25).contains(&count) {
+                    return Err(syn::Error::new(days.span(), "an event has between 1 and 25 days"));
+                }
+                setup.days.push((years, count));
+            } else if key == "template" {
+                input.parse::<Token![=]>()?;
+                let path = input.parse::<LitStr>()?;
+                setup.template = fs::read_to_string(path.value()).map_err(|e| syn::Error::new(path.span(), format!("cannot read the template: {e}")))?;
+            } else {
+                return Err(syn::Error::new(key.span(), "expected `years`, `days` or `template`"));
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(setup)
+    }
+}
+
+///list the names and number of arguments of the public functions of a day file (empty if the file is missing or does not parse, the compiler will report the error itself)
+fn day_functions(year: u32, day: u32) -> Vec<(String, usize)> {
+    fs::read_to_string(format!("src/functions/y{year}/d{day}.rs")).ok()
+        .and_then(|source| syn::parse_file(&source).ok())
+        .map(|file| file.items.into_iter().filter_map(|item| match item {
+            Item::Fn(f) if matches!(f.vis, Visibility::Public(_)) => Some((f.sig.ident.to_string(), f.sig.inputs.len())),
+            _ => None
+        }).collect())
+        .unwrap_or_default()
+}
+
+///numbers of the examples of a day that have an expected answer for the given part
+///examples are stored as inputs/{year}/examples/{day}_{n}.txt and their answers as inputs/{year}/examples/{day}_{n}a.txt and {day}_{n}b.txt
+///the directory is listed at compile time, the build script of the crate makes cargo rerun the macro when a file is added
+fn day_examples(year: u32, day: u32, part: &str) -> Vec<u32> {
+    let mut examples: Vec<u32> = fs::read_dir(format!("inputs/{year}/examples")).map(|dir| dir.filter_map(|entry| {
+        let name = entry.ok()?.file_name().into_string().ok()?;
+        let n = name.strip_prefix(format!("{day}_").as_str())?.strip_suffix(format!("{part}.txt").as_str())?.parse().ok()?;
+        fs::exists(format!("inputs/{year}/examples/{day}_{n}.txt")).ok()?.then_some(n)
+    }).collect()).unwrap_or_default();
+    examples.sort();
+    examples
+}
+
+///the main variant of a part is the function named after it (y2024d5b), other variants are suffixed by an underscore and their name (y2024d5b_sort)
+///the bool is true if the variant takes a second argument (the profiling context)
+fn part_variants(functions: &[(String, usize)], part: &str) -> Vec<(String, Ident, bool)> {
+    let mut variants = vec![("main".to_string(), Ident::new(part, Span::call_site()), functions.iter().any(|(f, args)| f == part && *args > 1))];
+    for (f, args) in functions {
+        if let Some(name) = f.strip_prefix(part).and_then(|s| s.strip_prefix('_')) {
+            variants.push((name.to_string(), Ident::new(f, Span::call_site()), *args > 1));
+        }
+    }
+    variants
+}
 
 ///usage: make sure you have a module 'functions' with nothing inside but a mod.rs file inside
 ///all the contents of the 'functions' module will be updated automatically and mod.rs will expose sub-modules for each years
 ///just import the 'functions' module before calling the advent_of_code_setup!() macro
 ///folders for the inputs and outputs will be generated too, just copy-paste the inputs from adventofcode.com inside the correct files and you are good to go
+///the macro generates a static DAYS listing every variant of every part (see part_variants), used by the runner module
+///a part can return any Display value and any error convertible into AocError, they are wrapped into an Answer/AocError pair
+///a test named y{year}_d{day}_{part}_ex{n} is generated for each example with an expected answer (see day_examples)
+///if a day has a public function 'parse' (fn parse(&str) -> Result<T, E>), it is run once and both parts receive a &T instead of the raw input
+///a day can also have a public function 'generate' (fn generate(&mut generators::Rng, usize) -> String) producing synthetic inputs of any size
+///and a public function 'watch' (fn watch(&str, f64) -> Result<(), AocError>) animating its input in the terminal at the given frame rate
+///parts can take a &profile::Ctx as second argument to record spans (let _s = ctx.span("name");), reported by the runner as a profile tree
+///arguments (all optional, separated by commas):
+///  years = 2015..=2024 -> the events to setup (a single year or a range, an open range like 2015.. ends at the current event), defaults to FIRST_YEAR..=current event
+///  days(2025..) = 12 -> number of days of the given events (a single year or a range), defaults to advent_days (25 until 2024, 12 since 2025)
+///  template = "path/to/template" -> template of the new day files (see utils::fill_template), defaults to templates/day.rs.tpl or utils::DEFAULT_TEMPLATE if it does not exist
 #[proc_macro]
-pub fn advent_of_code_setup(_input: TokenStream) -> TokenStream {
-    let year = if let Some(t) = get_advent_year_month_day() {
-        t.0
-    } else {
-        return TokenStream::new();
+pub fn advent_of_code_setup(input: TokenStream) -> TokenStream {
+    let setup = match syn::parse::<Setup>(input) {
+        Ok(setup) => setup,
+        Err(e) => return e.to_compile_error().into()
     };
 
-    let mut f = OpenOptions::new().create(true).write(true).open("src/functions/mod.rs").expect("Cannot create mod file for years");
+    update_mod_file("src/functions/mod.rs", &setup.years.clone().map(|y| format!("y{y}")).collect::<Vec<String>>(), "y");
 
-    let mut cases = Vec::new();
-    for y in FIRST_YEAR..=year {
-        setup_functions(y);
-        setup_inputs_and_outputs(y);
+    let mut days = Vec::new();
+    let mut tests = Vec::new();
+    for y in setup.years.clone() {
+        let day_count = setup.days(y);
+        setup_functions(y, day_count, setup.template.as_str());
+        setup_inputs_and_outputs(y, day_count);
         let y_i = Index::from(y as usize);
         let folder = Ident::new(format!("y{y}").as_str(), Span::call_site());
-        f.write_fmt(format_args!("pub mod y{y};\n")).expect("Cannot write mod file for years");
-        for day in 1..=25 {
-            let d = Index::from(day);
+        for day in 1..=day_count {
+            let d = Index::from(day as usize);
             let d_dir = Ident::new(format!("d{day}").as_str(), Span::call_site());
-            let fun_a = Ident::new(format!("y{y}d{day}a").as_str(), Span::call_site());
-            let fun_b = Ident::new(format!("y{y}d{day}b").as_str(), Span::call_site());
-            cases.push(quote!{
-                (#y_i, #d) => Ok((crate::functions::#folder::#d_dir::#fun_a(input)?, crate::functions::#folder::#d_dir::#fun_b(input)?)),
+            let functions = day_functions(y, day);
+            let path = quote! { crate::functions::#folder::#d_dir };
+            let parsed = functions.iter().any(|(f, _)| f == "parse");
+            let parse = if parsed {
+                quote! { Some(|input| #path::parse(input).map(|parsed| Box::new(parsed) as Box<dyn std::any::Any>).map_err(Into::into)) }
+            } else {
+                quote! { None }
+            };
+            let generate = if functions.iter().any(|(f, _)| f == "generate") {
+                quote! { Some(#path::generate) }
+            } else {
+                quote! { None }
+            };
+            let watch = if functions.iter().any(|(f, _)| f == "watch") {
+                quote! { Some(#path::watch) }
+            } else {
+                quote! { None }
+            };
+            let [a, b] = ["a", "b"].map(|part| {
+                let variants = part_variants(&functions, format!("y{y}d{day}{part}").as_str()).into_iter().map(|(name, fun, profiled)| {
+                    let input = if parsed {
+                        quote! { parsed.downcast_ref().expect("parts should take a reference to the output of parse") }
+                    } else {
+                        quote! { input }
+                    };
+                    let call = if profiled {
+                        quote! { #path::#fun(#input, ctx) }
+                    } else {
+                        quote! { #path::#fun(#input) }
+                    };
+                    quote! {
+                        crate::runner::Variant {
+                            name: #name,
+                            run: |input, parsed, ctx| #call.map(crate::runner::IntoAnswer::into_answer).map_err(Into::into)
+                        }
+                    }
+                });
+                for n in day_examples(y, day, part) {
+                    let test = Ident::new(format!("y{y}_d{day}_{part}_ex{n}").as_str(), Span::call_site());
+                    let input = format!("/inputs/{y}/examples/{day}_{n}.txt");
+                    let expected = format!("/inputs/{y}/examples/{day}_{n}{part}.txt");
+                    let part = part.chars().next().unwrap();
+                    tests.push(quote! {
+                        #[test]
+                        fn #test() {
+                            crate::runner::check_example(#y_i, #d, #part, include_str!(concat!(env!("CARGO_MANIFEST_DIR"), #input)), include_str!(concat!(env!("CARGO_MANIFEST_DIR"), #expected)));
+                        }
+                    });
+                }
+                quote! { &[#(#variants),*] }
+            });
+            days.push(quote!{
+                crate::runner::Day { year: #y_i, day: #d, parse: #parse, generate: #generate, watch: #watch, a: #a, b: #b }
             })
         }
     }
 
     TokenStream::from(quote!{
-        pub fn day_caller(year: u32, day: u32, input: &str) -> Result<(String, String), String> {
-            match (year, day) {
-                #(#cases)*
-                _ => {
-                    Err(format!("Invalid year/day pair: {year} {day}"))
+        pub static DAYS: &[crate::runner::Day] = &[#(#days),*];
+
+        #[cfg(test)]
+        mod examples {
+            #(#tests)*
+        }
+    })
+}
+
+fn tile_chars(variant: &syn::Variant) -> syn::Result<Vec<syn::LitChar>> {
+    if !matches!(variant.fields, syn::Fields::Unit) {
+        return Err(syn::Error::new(variant.span(), "tile variants cannot have fields"));
+    }
+    let attr = variant.attrs.iter().find(|a| a.path().is_ident("tile")).ok_or_else(|| syn::Error::new(variant.span(), "expected a #[tile('c')] attribute"))?;
+    let chars: Vec<syn::LitChar> = attr.parse_args_with(syn::punctuated::Punctuated::<syn::LitChar, Token![,]>::parse_terminated)?.into_iter().collect();
+    if chars.is_empty() {
+        return Err(syn::Error::new(attr.span(), "expected at least one char"));
+    }
+    Ok(chars)
+}
+
+///derive the char conversions of a fieldless enum: TryFrom<char> (with an AocError), From<Self> for char and Display
+///each variant has a #[tile('c')] attribute, more chars can be given to parse several chars as the same tile (#[tile('.', 'X')]), the first one is used for printing
+///the enum can then be used with Grid::parse_tiles and printed as a grid
+///the generated code refers to ::advent_of_code, so it works in the library (which declares itself under that name) and in the crates using it
+#[proc_macro_derive(Tile, attributes(tile))]
+pub fn derive_tile(input: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(input as syn::DeriveInput);
+    let name = &item.ident;
+    let syn::Data::Enum(data) = &item.data else {
+        return syn::Error::new(item.span(), "Tile can only be derived for enums").to_compile_error().into();
+    };
+    let mut parse = Vec::new();
+    let mut print = Vec::new();
+    for variant in &data.variants {
+        let chars = match tile_chars(variant) {
+            Ok(chars) => chars,
+            Err(e) => return e.to_compile_error().into(),
+        };
+        let ident = &variant.ident;
+        let first = &chars[0];
+        parse.push(quote! { #(#chars)|* => Ok(#name::#ident) });
+        print.push(quote! { #name::#ident => #first });
+    }
+    let message = format!("invalid {name} tile '{{c}}'");
+    TokenStream::from(quote! {
+        impl TryFrom<char> for #name {
+            type Error = ::advent_of_code::advent_utils::AocError;
+
+            fn try_from(c: char) -> Result<Self, Self::Error> {
+                match c {
+                    #(#parse,)*
+                    c => Err(::advent_of_code::advent_utils::AocError::parse(format!(#message))),
                 }
             }
         }
+
+        impl From<#name> for char {
+            fn from(tile: #name) -> char {
+                match tile {
+                    #(#print,)*
+                }
+            }
+        }
+
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Write::write_char(f, char::from(*self))
+            }
+        }
     })
-}
\ No newline at end of file
+}
@@ -1,3 +1,25 @@
+mod direction;
+mod error;
+mod graph;
+mod grid;
+mod grid_search;
+mod image;
+mod point;
+mod regions;
+mod sparse_grid;
+mod terminal;
+
+pub use direction::*;
+pub use error::*;
+pub use graph::*;
+pub use grid::*;
+pub use image::*;
+pub use point::*;
+pub use regions::*;
+pub use sparse_grid::*;
+pub use terminal::*;
+pub use proc_macros::Tile;
+
 ///test if the given input is a visual square (all lines delimited by LF, CR or EOI are of same length)
 ///return a size (width, height) if the input is square or none if the input is not square
 ///note: CRLF and LFCR are interpreted as a single line delimiter, but LFLF or CRCR would be considered like 2 lines (with the second of size 0)
@@ -55,94 +77,190 @@ pub fn is_text_square(text: &str) -> Option<(usize, usize)> {
             line += 1;
         }
     }
-    if height == 0 {
-        return Some((line, 1));
-    }
-    if width != line {
-        return None;
+    //the last line was already counted if the text ends with a CRLF or LFCR, otherwise it is terminated by the end of the text
+    if cr || lf || !text.ends_with(['\n', '\r']) {
+        if height == 0 {
+            width = line;
+        }
+        if line != width {
+            return None;
+        }
+        height += 1;
     }
-    Some((width, height + if !cr && !lf { 1 } else { 0 }))
+    Some((width, height))
 }
 
+///lines of a text delimited like in is_text_square, with their number (starting at 0, empty lines are counted even when they are skipped)
+///scans the bytes of the text: LF and CR are ASCII so they can never be part of a multi-byte char
 struct LineIterator<'s> {
     include_empty: bool,
     text: &'s str,
-    raw: Vec<char>,
     line: usize,
     index: usize,
+    done: bool,
 }
 
-impl <'s> Iterator for LineIterator<'s> {
+impl<'s> Iterator for LineIterator<'s> {
     type Item = (usize, &'s str);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.include_empty && self.index == 0 && self.raw.len() == 0 {
-            self.index = 1;
-            return Some((0, &self.text[..]));
-        }
-        if self.index >= self.raw.len() { return None; }
-        let sub = &self.text[self.index..];
-        let prev = self.index;
-        while self.index < self.raw.len() {
-            let r = match (sub.find('\n'), sub.find('\r')) {
-                (Some(lf), Some(cr)) => {
-                    self.line += 1;
-                    if lf == cr + 1 || lf == cr - 1 {
-                        self.index += lf.max(cr) + 1;
-                        Some((self.line - 1, &self.text[prev..self.index - 2]))
-                    } else {
-                        self.index += lf.min(cr) + 1;
-                        Some((self.line - 1, &self.text[prev..self.index - 1]))
-                    }
+        let bytes = self.text.as_bytes();
+        while !self.done {
+            let start = self.index;
+            let end = bytes[start..].iter().position(|b| *b == b'\n' || *b == b'\r').map_or(bytes.len(), |e| start + e);
+            if end == bytes.len() {
+                self.done = true;
+            } else {
+                self.index = end + 1;
+                if self.index < bytes.len() && bytes[self.index] != bytes[end] && (bytes[self.index] == b'\n' || bytes[self.index] == b'\r') {
+                    self.index += 1;
                 }
-                (Some(lf), None) => {
-                    self.line += 1;
-                    self.index += lf + 1;
-                    Some((self.line - 1, &self.text[prev..self.index - 1]))
-                }
-                (None, Some(cr)) => {
-                    self.line += 1;
-                    self.index += cr + 1;
-                    Some((self.line - 1, &self.text[prev..self.index - 1]))
-                }
-                (None, None) => {
-                    self.index = self.raw.len();
-                    Some((self.line, &self.text[prev..self.index]))
-                }
-            };
-            if !self.include_empty && self.index - prev == 0 {
-                continue;
+                //a delimiter at the end of the text does not start a new line
+                self.done = self.index == bytes.len();
+            }
+            self.line += 1;
+            if self.include_empty || end > start {
+                return Some((self.line - 1, &self.text[start..end]));
             }
-            return r;
         }
         None
     }
 }
 
+///iterate over the lines of a text (see is_text_square for the delimiters), empty lines are skipped unless include_empty is true
+///an empty text is a single empty line
 pub fn iter_lines(text: &str, include_empty: bool) -> impl Iterator<Item = (usize, &str)> {
     LineIterator {
         include_empty,
-        index: 0,
-        line: 0,
-        raw: text.chars().collect(),
         text,
+        line: 0,
+        index: 0,
+        done: false,
     }
 }
 
+///iterate over the sections of a text: blocks of lines separated by blank lines (empty or only whitespace)
+///sections are slices of the text without their trailing whitespace, so errors in them can still be located in the whole text
+pub fn iter_sections(text: &str) -> impl Iterator<Item = &str> {
+    let mut lines = iter_lines(text, true).map(|(_, l)| l).peekable();
+    let offset = |line: &str| line.as_ptr() as usize - text.as_ptr() as usize;
+    std::iter::from_fn(move || {
+        let first = lines.find(|l| !l.trim().is_empty())?;
+        let mut last = first;
+        while let Some(line) = lines.next_if(|l| !l.trim().is_empty()) {
+            last = line;
+        }
+        Some(text[offset(first)..offset(last) + last.len()].trim_end())
+    })
+}
+
+///split a text in exactly N sections (see iter_sections): let [rules, updates] = sections(input)?;
+pub fn sections<const N: usize>(text: &str) -> Result<[&str; N], AocError> {
+    let found: Vec<&str> = iter_sections(text).collect();
+    let count = found.len();
+    found.try_into().map_err(|found: Vec<&str>| {
+        let error = AocError::parse(format!("expected {N} sections separated by blank lines, found {count}"));
+        match found.get(N) {
+            Some(extra) => error.at(text, extra.as_ptr() as usize - text.as_ptr() as usize),
+            None => error,
+        }
+    })
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
+    use crate::generators::Rng;
 
     #[test]
     fn test_is_text_square() {
         assert_eq!(is_text_square(""), Some((0, 1)));
         assert_eq!(is_text_square("test!\n\r3 . .\nlines"), Some((5, 3)));
         assert_eq!(is_text_square("1\n21"), None);
+        assert_eq!(is_text_square("ab\r\ncd\r\n"), Some((2, 2)));
+        assert_eq!(is_text_square("ab\ncd\n"), Some((2, 2)));
     }
 
     #[test]
     fn test_iter_lines() {
         assert_eq!(iter_lines("", true).collect::<Vec<(usize, &str)>>(), vec![(0, "")], "test empty string with include empty");
         assert_eq!(iter_lines("", false).collect::<Vec<(usize, &str)>>(), vec![], "test empty string without include empty");
+        assert_eq!(iter_lines("é→\r\n\n\r\nx\r", true).collect::<Vec<(usize, &str)>>(), vec![(0, "é→"), (1, ""), (2, ""), (3, "x")]);
+        assert_eq!(iter_lines("\n\nab\r\rcd", false).collect::<Vec<(usize, &str)>>(), vec![(2, "ab"), (4, "cd")]);
+    }
+
+    //random text of up to max_lines lines mixing LF, CR, CRLF and LFCR delimiters, empty lines and non ASCII chars
+    //square texts are more likely if width is given
+    fn random_text(rng: &mut Rng, max_lines: usize, width: Option<usize>) -> String {
+        let mut text = String::new();
+        for _ in 0..rng.index(max_lines + 1) {
+            let len = width.unwrap_or_else(|| rng.index(6));
+            for _ in 0..len {
+                text.push(*rng.pick(&['a', '.', '#', ' ', 'é', '→']));
+            }
+            text.push_str(rng.pick(&["\n", "\r", "\r\n", "\n\r"]));
+        }
+        if rng.chance(0.5) {
+            text.truncate(text.trim_end_matches(['\n', '\r']).len());
+        }
+        text
+    }
+
+    //reference model: split on delimiters (a LF followed by a CR or the reverse is a single delimiter), a delimiter at the end does not start a new line
+    fn model_lines(text: &str) -> Vec<String> {
+        let mut lines = vec![String::new()];
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\n' || c == '\r' {
+                chars.next_if(|n| (*n == '\n' || *n == '\r') && *n != c);
+                if chars.peek().is_some() {
+                    lines.push(String::new());
+                }
+            } else {
+                lines.last_mut().unwrap().push(c);
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn test_sections() {
+        let text = "\n1|2\r\n3|4  \r\n \t\r\n\r\n5,6\n7\n\n";
+        assert_eq!(iter_sections(text).collect::<Vec<&str>>(), vec!["1|2\r\n3|4", "5,6\n7"]);
+        assert_eq!(sections::<2>(text), Ok(["1|2\r\n3|4", "5,6\n7"]));
+        assert_eq!(sections::<3>(text).map_err(|e| e.message), Err("expected 3 sections separated by blank lines, found 2".to_string()));
+        assert_eq!(sections::<1>(text).map_err(|e| e.location.map(|l| l.line)), Err(Some(6)));
+        assert_eq!(iter_sections(" \n\n").count(), 0);
+    }
+
+    #[test]
+    fn test_is_text_square_properties() {
+        let mut rng = Rng::new(0);
+        for i in 0..2000 {
+            let width = if i % 2 == 0 { Some(rng.index(4)) } else { None };
+            let text = random_text(&mut rng, 6, width);
+            let widths: Vec<usize> = model_lines(&text).iter().map(|l| l.chars().count()).collect();
+            let square = if widths.iter().all(|w| *w == widths[0]) { Some((widths[0], widths.len())) } else { None };
+            assert_eq!(is_text_square(&text), square, "is_text_square({text:?})");
+        }
+    }
+
+    #[test]
+    fn test_iter_lines_properties() {
+        let mut rng = Rng::new(1);
+        for i in 0..2000 {
+            let width = if i % 2 == 0 { Some(rng.index(4)) } else { None };
+            let text = random_text(&mut rng, 6, width);
+            let all: Vec<(usize, &str)> = iter_lines(&text, true).collect();
+            assert_eq!(all.iter().map(|(_, l)| l.to_string()).collect::<Vec<String>>(), model_lines(&text), "iter_lines({text:?}, true)");
+            assert!(all.iter().enumerate().all(|(i, (n, _))| i == *n), "line numbers of iter_lines({text:?}, true)");
+            let non_empty: Vec<(usize, &str)> = all.iter().copied().filter(|(_, l)| !l.is_empty()).collect();
+            assert_eq!(iter_lines(&text, false).collect::<Vec<(usize, &str)>>(), non_empty, "iter_lines({text:?}, false)");
+            //both functions agree on what a line is
+            if let Some((width, height)) = is_text_square(&text) {
+                assert_eq!(all.len(), height, "height of {text:?}");
+                assert!(all.iter().all(|(_, l)| l.chars().count() == width), "width of {text:?}");
+            }
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,173 @@
+use super::AocError;
+
+///behaviour shared by Dir4 and Dir8, ALL lists the directions clockwise starting from Up (turns are steps in this list)
+///y grows downward like in the grids: Up is (0, -1)
+pub trait Direction: Copy + Eq + From<Dir4> + 'static {
+    const ALL: &'static [Self];
+
+    fn delta(self) -> (isize, isize);
+
+    fn index(self) -> usize;
+
+    fn iter() -> impl Iterator<Item = Self> {
+        Self::ALL.iter().copied()
+    }
+
+    ///turn clockwise by a number of steps (a quarter for Dir4, an eighth for Dir8), negative steps turn counter clockwise
+    fn turn(self, steps: isize) -> Self {
+        Self::ALL[(self.index() as isize + steps).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    fn turn_right(self) -> Self {
+        self.turn(1)
+    }
+
+    fn turn_left(self) -> Self {
+        self.turn(-1)
+    }
+
+    ///turn clockwise by an angle in degrees, None if the angle is not a multiple of a step
+    fn turn_degrees(self, degrees: isize) -> Option<Self> {
+        let step = 360 / Self::ALL.len() as isize;
+        (degrees % step == 0).then(|| self.turn(degrees / step))
+    }
+
+    fn reverse(self) -> Self {
+        self.turn(Self::ALL.len() as isize / 2)
+    }
+
+    ///parse one of ^>v<, NESW or UDLR
+    fn from_char(c: char) -> Option<Self> {
+        let d = match c {
+            '^' | 'N' | 'U' => Dir4::Up,
+            '>' | 'E' | 'R' => Dir4::Right,
+            'v' | 'S' | 'D' => Dir4::Down,
+            '<' | 'W' | 'L' => Dir4::Left,
+            _ => return None,
+        };
+        Some(d.into())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Dir4 {
+    #[default]
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub fn arrow(self) -> char {
+        ['^', '>', 'v', '<'][self as usize]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir4::Up | Dir4::Down)
+    }
+}
+
+impl Direction for Dir4 {
+    const ALL: &'static [Self] = &[Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    fn delta(self) -> (isize, isize) {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)][self as usize]
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Dir8 {
+    #[default]
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const CARDINALS: [Self; 4] = [Dir8::Up, Dir8::Right, Dir8::Down, Dir8::Left];
+    pub const DIAGONALS: [Self; 4] = [Dir8::UpRight, Dir8::DownRight, Dir8::DownLeft, Dir8::UpLeft];
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl Direction for Dir8 {
+    const ALL: &'static [Self] = &[Dir8::Up, Dir8::UpRight, Dir8::Right, Dir8::DownRight, Dir8::Down, Dir8::DownLeft, Dir8::Left, Dir8::UpLeft];
+
+    fn delta(self) -> (isize, isize) {
+        [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)][self as usize]
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Self {
+        Dir8::ALL[d as usize * 2]
+    }
+}
+
+impl TryFrom<Dir8> for Dir4 {
+    type Error = AocError;
+
+    fn try_from(d: Dir8) -> Result<Self, Self::Error> {
+        if d.is_diagonal() {
+            Err(AocError::from(format!("{d:?} is not a cardinal direction")))
+        } else {
+            Ok(Dir4::ALL[d as usize / 2])
+        }
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = AocError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or_else(|| AocError::parse(format!("invalid direction '{c}'")))
+    }
+}
+
+impl TryFrom<char> for Dir8 {
+    type Error = AocError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or_else(|| AocError::parse(format!("invalid direction '{c}'")))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Dir4::Left.turn_right(), Dir4::Up);
+        assert_eq!(Dir4::Up.turn(-3), Dir4::Right);
+        assert_eq!(Dir8::Up.turn_degrees(-135), Some(Dir8::DownLeft));
+        assert_eq!(Dir4::Up.turn_degrees(45), None);
+        assert!(Dir8::iter().all(|d| d.reverse().reverse() == d && d.reverse().delta() == (-d.delta().0, -d.delta().1)));
+        assert!(Dir4::iter().all(|d| Dir8::from(d).delta() == d.delta() && Dir4::try_from(Dir8::from(d)) == Ok(d)));
+        assert!(Dir4::try_from(Dir8::UpLeft).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("^>v<".chars().map(Dir4::from_char).collect::<Option<Vec<Dir4>>>(), Some(Dir4::ALL.to_vec()));
+        assert_eq!("NESW".chars().map(Dir8::try_from).collect::<Result<Vec<Dir8>, AocError>>(), Ok(Dir8::CARDINALS.to_vec()));
+        assert_eq!(Dir4::try_from('D'), Ok(Dir4::Down));
+        assert!(Dir4::try_from('x').is_err());
+    }
+}
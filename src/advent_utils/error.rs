@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AocErrorKind {
+    ///the input does not have the expected format
+    Parse,
+    ///the input is valid but has no answer (infinite loop, unreachable goal, ...)
+    NoSolution,
+    Other,
+}
+
+///position of an error in the input, line and column start at 1 and the column is counted in chars
+///snippet is the whole line containing the error (without its line delimiter)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+}
+
+impl Location {
+    ///locate a byte offset in a text, CRLF and LFCR are considered as a single line delimiter (like is_text_square)
+    pub fn new(text: &str, offset: usize) -> Self {
+        let offset = offset.min(text.len());
+        let mut line = 1;
+        let mut start = 0;
+        let bytes = text.as_bytes();
+        let mut i = 0;
+        while i < offset {
+            if bytes[i] == b'\n' || bytes[i] == b'\r' {
+                if i + 1 < offset && bytes[i + 1] != bytes[i] && (bytes[i + 1] == b'\n' || bytes[i + 1] == b'\r') {
+                    i += 1;
+                }
+                line += 1;
+                start = i + 1;
+            }
+            i += 1;
+        }
+        let end = text[offset..].find(['\n', '\r']).map_or(text.len(), |e| offset + e);
+        Self {
+            line,
+            column: text[start..offset].chars().count() + 1,
+            snippet: text[start..end].to_string(),
+        }
+    }
+}
+
+///common error type of the solutions, anything convertible into it can be returned by a part
+///errors converted from nom only remember where the remaining input was, call locate with the parsed text to get a Location
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AocError {
+    pub kind: AocErrorKind,
+    pub message: String,
+    pub location: Option<Location>,
+    remaining: Option<usize>,
+}
+
+impl AocError {
+    pub fn new(kind: AocErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            location: None,
+            remaining: None,
+        }
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::new(AocErrorKind::Parse, message)
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Self::new(AocErrorKind::NoSolution, message)
+    }
+
+    ///attach the location of a byte offset of the input to this error
+    pub fn at(mut self, input: &str, offset: usize) -> Self {
+        self.location = Some(Location::new(input, offset));
+        self.remaining = None;
+        self
+    }
+
+    ///resolve the position remembered by a nom error, does nothing if the error does not point inside the given input
+    pub fn locate(self, input: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        match self.remaining {
+            Some(ptr) if ptr >= start && ptr <= start + input.len() && input.is_char_boundary(ptr - start) => self.at(input, ptr - start),
+            _ => self,
+        }
+    }
+
+    ///multi-line rendering of the error, with the offending line and a caret under the bad character
+    pub fn render(&self) -> String {
+        if let Some(location) = &self.location {
+            format!("{self}\n    {}\n    {}^", location.snippet, " ".repeat(location.column - 1))
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            AocErrorKind::Parse => write!(f, "parse error")?,
+            AocErrorKind::NoSolution => write!(f, "no solution")?,
+            AocErrorKind::Other => write!(f, "error")?,
+        }
+        if let Some(location) = &self.location {
+            write!(f, " at {}:{}", location.line, location.column)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Error for AocError {}
+
+impl From<String> for AocError {
+    fn from(message: String) -> Self {
+        Self::new(AocErrorKind::Other, message)
+    }
+}
+
+impl From<&str> for AocError {
+    fn from(message: &str) -> Self {
+        Self::new(AocErrorKind::Other, message)
+    }
+}
+
+impl From<regex::Error> for AocError {
+    fn from(e: regex::Error) -> Self {
+        Self::new(AocErrorKind::Other, e.to_string())
+    }
+}
+
+impl From<nom::error::Error<&str>> for AocError {
+    fn from(e: nom::error::Error<&str>) -> Self {
+        let mut error = Self::parse(format!("expected {}", e.code.description()));
+        error.remaining = Some(e.input.as_ptr() as usize);
+        error
+    }
+}
+
+impl From<nom::Err<nom::error::Error<&str>>> for AocError {
+    fn from(e: nom::Err<nom::error::Error<&str>>) -> Self {
+        match e {
+            nom::Err::Incomplete(_) => Self::parse("unexpected end of input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        assert_eq!(Location::new("ab\r\ncd\n\nef", 5), Location { line: 2, column: 2, snippet: "cd".to_string() });
+        assert_eq!(Location::new("ab\r\ncd\n\nef", 8), Location { line: 4, column: 1, snippet: "ef".to_string() });
+        assert_eq!(Location::new("é#", 2), Location { line: 1, column: 2, snippet: "é#".to_string() });
+    }
+
+    #[test]
+    fn test_nom_error() {
+        use nom::character::complete::u32 as int;
+        let input = "12\n3x";
+        let e: AocError = int::<&str, nom::error::Error<&str>>(&input[4..]).unwrap_err().into();
+        let e = e.locate(input);
+        assert_eq!(e.location.as_ref().map(|l| (l.line, l.column)), Some((2, 2)));
+        assert_eq!(e.render(), "parse error at 2:2: expected Digit\n    3x\n     ^");
+    }
+}
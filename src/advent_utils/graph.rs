@@ -0,0 +1,207 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+///result of a search: the distance of the states reached from the starts and, for each of them, all the predecessors on a shortest path
+///goal is the first goal state reached (if the search had a goal), the states with the same distance as it may not be complete
+pub struct Search<S> {
+    ids: HashMap<S, usize>,
+    states: Vec<S>,
+    distances: Vec<u64>,
+    parents: Vec<Vec<usize>>,
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new() -> Self {
+        Self { ids: HashMap::new(), states: Vec::new(), distances: Vec::new(), parents: Vec::new(), goal: None }
+    }
+
+    fn id(&mut self, s: &S) -> usize {
+        if let Some(id) = self.ids.get(s) {
+            return *id;
+        }
+        self.ids.insert(s.clone(), self.states.len());
+        self.states.push(s.clone());
+        self.distances.push(u64::MAX);
+        self.parents.push(Vec::new());
+        self.states.len() - 1
+    }
+
+    ///record that s can be reached from parent at the given distance, true if it is a new shortest distance
+    fn relax(&mut self, s: usize, parent: Option<usize>, distance: u64) -> bool {
+        if distance < self.distances[s] {
+            self.distances[s] = distance;
+            self.parents[s] = parent.into_iter().collect();
+            true
+        } else {
+            if distance == self.distances[s] && let Some(parent) = parent && !self.parents[s].contains(&parent) {
+                self.parents[s].push(parent);
+            }
+            false
+        }
+    }
+
+    pub fn distance(&self, s: &S) -> Option<u64> {
+        self.ids.get(s).map(|id| self.distances[*id]).filter(|d| *d != u64::MAX)
+    }
+
+    ///every state reached with its distance
+    pub fn distances(&self) -> impl Iterator<Item = (&S, u64)> {
+        self.states.iter().zip(self.distances.iter().copied()).filter(|(_, d)| *d != u64::MAX)
+    }
+
+    ///one shortest path from a start to s (both included)
+    pub fn path(&self, s: &S) -> Option<Vec<S>> {
+        self.distance(s)?;
+        let mut id = self.ids[s];
+        let mut path = vec![s.clone()];
+        while let Some(parent) = self.parents[id].first() {
+            id = *parent;
+            path.push(self.states[id].clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    ///all the shortest paths from a start to s, their number can grow exponentially with the size of the graph
+    pub fn all_paths(&self, s: &S) -> Vec<Vec<S>> {
+        fn rec<S: Clone>(search: &Search<S>, id: usize, suffix: &mut Vec<S>, out: &mut Vec<Vec<S>>) {
+            suffix.push(search.states[id].clone());
+            if search.parents[id].is_empty() {
+                out.push(suffix.iter().rev().cloned().collect());
+            }
+            for parent in &search.parents[id] {
+                rec(search, *parent, suffix, out);
+            }
+            suffix.pop();
+        }
+        let mut out = Vec::new();
+        if self.distance(s).is_some() {
+            rec(self, self.ids[s], &mut Vec::new(), &mut out);
+        }
+        out
+    }
+
+    ///states on any shortest path to the closest of the targets (several targets can be at the same distance)
+    pub fn on_optimal_paths(&self, targets: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let targets: Vec<usize> = targets.into_iter().filter_map(|t| self.distance(&t).map(|_| self.ids[&t])).collect();
+        let best = targets.iter().map(|t| self.distances[*t]).min();
+        let mut queue: Vec<usize> = targets.into_iter().filter(|t| Some(self.distances[*t]) == best).collect();
+        let mut seen: HashSet<usize> = queue.iter().copied().collect();
+        while let Some(id) = queue.pop() {
+            for parent in &self.parents[id] {
+                if seen.insert(*parent) {
+                    queue.push(*parent);
+                }
+            }
+        }
+        seen.into_iter().map(|id| self.states[id].clone()).collect()
+    }
+}
+
+///breadth first search where each move costs 1, stops when a goal state is reached (use |_| false to get the distances of every reachable state)
+pub fn bfs<S: Clone + Eq + Hash, I: IntoIterator<Item = S>>(starts: impl IntoIterator<Item = S>, mut neighbors: impl FnMut(&S) -> I, mut goal: impl FnMut(&S) -> bool) -> Search<S> {
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for s in starts {
+        let id = search.id(&s);
+        if search.relax(id, None, 0) {
+            queue.push_back(id);
+        }
+    }
+    while let Some(id) = queue.pop_front() {
+        let state = search.states[id].clone();
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        let distance = search.distances[id] + 1;
+        for n in neighbors(&state) {
+            let n = search.id(&n);
+            if search.relax(n, Some(id), distance) {
+                queue.push_back(n);
+            }
+        }
+    }
+    search
+}
+
+///shortest paths where neighbors gives the next states with the cost of the move, stops when a goal state is reached
+///the costs must be positive for the predecessors of the goal to be complete
+pub fn dijkstra<S: Clone + Eq + Hash, I: IntoIterator<Item = (S, u64)>>(starts: impl IntoIterator<Item = S>, neighbors: impl FnMut(&S) -> I, goal: impl FnMut(&S) -> bool) -> Search<S> {
+    astar(starts, neighbors, |_| 0, goal)
+}
+
+///dijkstra guided by a heuristic: a lower bound of the distance from a state to the goal that never decreases by more than the cost of a move
+pub fn astar<S: Clone + Eq + Hash, I: IntoIterator<Item = (S, u64)>>(starts: impl IntoIterator<Item = S>, mut neighbors: impl FnMut(&S) -> I, mut heuristic: impl FnMut(&S) -> u64, mut goal: impl FnMut(&S) -> bool) -> Search<S> {
+    let mut search = Search::new();
+    let mut heap = BinaryHeap::new();
+    for s in starts {
+        let id = search.id(&s);
+        if search.relax(id, None, 0) {
+            heap.push(Reverse((heuristic(&s), 0, id)));
+        }
+    }
+    while let Some(Reverse((_, distance, id))) = heap.pop() {
+        if distance > search.distances[id] {
+            continue;
+        }
+        let state = search.states[id].clone();
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        for (n, cost) in neighbors(&state) {
+            let n_id = search.id(&n);
+            if search.relax(n_id, Some(id), distance + cost) {
+                heap.push(Reverse((distance + cost + heuristic(&n), distance + cost, n_id)));
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::advent_utils::{Dir4, Direction, Grid, Point};
+
+    const MAZE: &str = "###############\n#.......#....E#\n#.#.###.#.###.#\n#.....#.#...#.#\n#.###.#####.#.#\n#.#.#.......#.#\n#.#.#####.###.#\n#...........#.#\n###.#.#####.#.#\n#...#.....#.#.#\n#.#.#.###.#.#.#\n#.....#...#.#.#\n#.###.#.#.#.#.#\n#S..#.....#...#\n###############";
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::parse("S..#\n.#..\n...E", '#', Some).unwrap();
+        let neighbors = |p: &Point| p.neighbors::<Dir4>().filter(|n| *grid.get_point(*n) != '#').collect::<Vec<Point>>();
+        let search = bfs([Point::new(0, 0)], neighbors, |p| *p == Point::new(3, 2));
+        assert_eq!(search.goal, Some(Point::new(3, 2)));
+        assert_eq!(search.distance(&Point::new(3, 2)), Some(5));
+        assert_eq!(search.path(&Point::new(3, 2)).map(|p| p.len()), Some(6));
+        assert_eq!(search.all_paths(&Point::new(3, 2)).len(), 3);
+        let all = bfs([Point::new(0, 0)], neighbors, |_| false);
+        assert_eq!(all.distances().count(), 10);
+    }
+
+    #[test]
+    fn test_weighted() {
+        //the reindeer maze of 2024 day 16: moving costs 1, turning costs 1000
+        let grid = Grid::parse(MAZE, '#', Some).unwrap();
+        let find = |c: char| grid.iter().find(|(_, t)| **t == c).map(|(p, _)| Point::from(p)).unwrap();
+        let (start, end) = (find('S'), find('E'));
+        let neighbors = |(p, d): &(Point, Dir4)| {
+            let mut next = vec![((*p, d.turn_left()), 1000), ((*p, d.turn_right()), 1000)];
+            if *grid.get_point(p.step(*d)) != '#' {
+                next.push(((p.step(*d), *d), 1));
+            }
+            next
+        };
+        let search = dijkstra([(start, Dir4::Right)], neighbors, |_| false);
+        let ends: Vec<(Point, Dir4)> = Dir4::iter().map(|d| (end, d)).collect();
+        assert_eq!(ends.iter().filter_map(|e| search.distance(e)).min(), Some(7036));
+        assert_eq!(search.on_optimal_paths(ends).iter().map(|(p, _)| *p).collect::<HashSet<Point>>().len(), 45);
+
+        let guided = astar([(start, Dir4::Right)], neighbors, |(p, _)| p.manhattan(end) as u64, |(p, _)| *p == end);
+        assert_eq!(guided.goal.as_ref().and_then(|g| guided.distance(g)), Some(7036));
+        assert!(guided.distances().count() < search.distances().count());
+    }
+}
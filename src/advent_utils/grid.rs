@@ -0,0 +1,251 @@
+use std::fmt::{Display, Formatter};
+use super::{is_text_square, iter_lines, AocError, Point};
+
+///tiles that can be parsed from a char and printed as a char, usually implemented with #[derive(Tile)]
+pub trait Tile: Copy + TryFrom<char, Error = AocError> + Into<char> {}
+
+impl<T: Copy + TryFrom<char, Error = AocError> + Into<char>> Tile for T {}
+
+///access shared by Grid and SparseGrid, reading a position without a tile gives the default tile (the edge of a Grid, the empty tile of a SparseGrid)
+pub trait GridAccess<T> {
+    fn get_point(&self, p: Point) -> &T;
+
+    ///replace the tile at p and return the previous one
+    fn set_point(&mut self, p: Point, tile: T) -> T;
+
+    ///smallest and largest corners of the stored tiles, None if there is none
+    fn bounds(&self) -> Option<(Point, Point)>;
+
+    ///one line per row of the bounding box, without a line delimiter after the last one
+    fn render(&self) -> String where T: Display {
+        let Some((min, max)) = self.bounds() else { return String::new() };
+        (min.y..=max.y).map(|y| (min.x..=max.x).map(|x| self.get_point(Point::new(x, y)).to_string()).collect::<String>()).collect::<Vec<String>>().join("\n")
+    }
+}
+
+///rectangular grid of tiles stored row by row, (0, 0) is the top left corner
+///reading outside of the grid gives the edge tile instead of failing (like a wall around the grid)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+    edge: T,
+}
+
+impl<T> Grid<T> {
+    ///parse a square text (see is_text_square), f maps each char to a tile or returns None if the char is invalid
+    pub fn parse(text: &str, edge: T, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, AocError> {
+        Self::parse_with(text, edge, |_, _, c| f(c).ok_or_else(|| AocError::parse(format!("invalid character '{c}'"))))
+    }
+
+    ///like parse but f also gets the position of the char, errors of f without a location are located on the char
+    pub fn parse_with(text: &str, edge: T, mut f: impl FnMut(usize, usize, char) -> Result<T, AocError>) -> Result<Self, AocError> {
+        let offset = |line: &str| line.as_ptr() as usize - text.as_ptr() as usize;
+        let Some((width, height)) = is_text_square(text) else {
+            let mut lines = iter_lines(text, true);
+            let width = lines.next().map_or(0, |(_, l)| l.chars().count());
+            let error = AocError::parse(format!("all lines should be {width} chars long"));
+            return Err(match lines.find(|(_, l)| l.chars().count() != width) {
+                Some((_, line)) => error.at(text, offset(line) + line.char_indices().nth(width).map_or(line.len(), |(i, _)| i)),
+                None => error,
+            });
+        };
+        let mut tiles = Vec::with_capacity(width * height);
+        for (y, line) in iter_lines(text, true) {
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                tiles.push(f(x, y, c).map_err(|e| if e.location.is_none() { e.at(text, offset(line) + i) } else { e })?);
+            }
+        }
+        Ok(Self { width, height, tiles, edge })
+    }
+
+    ///grid where the tile at (x, y) is f(x, y)
+    pub fn from_fn(width: usize, height: usize, edge: T, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        Self { width, height, tiles: (0..width * height).map(|i| f(i % width, i / width)).collect(), edge }
+    }
+
+    pub fn from_vec(width: usize, tiles: Vec<T>, edge: T) -> Self {
+        Self { width, height: tiles.len().checked_div(width).unwrap_or(0), tiles, edge }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn edge(&self) -> &T {
+        &self.edge
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        Point::new(x, y).to_usize(self.width, self.height).is_some()
+    }
+
+    pub fn contains_point(&self, p: Point) -> bool {
+        self.contains(p.x, p.y)
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(x + y * self.width)
+    }
+
+    ///tile at (x, y), or the edge tile if outside of the grid
+    pub fn get(&self, x: usize, y: usize) -> &T {
+        self.index(x, y).map_or(&self.edge, |i| &self.tiles[i])
+    }
+
+    pub fn get_signed(&self, x: isize, y: isize) -> &T {
+        if self.contains(x, y) { self.get(x as usize, y as usize) } else { &self.edge }
+    }
+
+    pub fn get_point(&self, p: Point) -> &T {
+        self.get_signed(p.x, p.y)
+    }
+
+    ///None outside of the grid (the edge tile cannot be modified)
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index(x, y).map(|i| &mut self.tiles[i])
+    }
+
+    pub fn get_signed_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.contains(x, y) { self.get_mut(x as usize, y as usize) } else { None }
+    }
+
+    pub fn get_point_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.get_signed_mut(p.x, p.y)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.tiles[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.tiles.iter().skip(x).step_by(self.width.max(1)).take(if x < self.width { self.height } else { 0 })
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    ///all the tiles with their position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.tiles.iter().enumerate().map(|(i, t)| ((i % self.width, i / self.width), t))
+    }
+
+    pub fn map<U>(&self, edge: U, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, tiles: self.tiles.iter().map(f).collect(), edge }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T, edge: T) -> Self {
+        Self { width, height, tiles: vec![fill; width * height], edge }
+    }
+
+    ///replace the tile at (x, y) and return the previous one, nothing is changed outside of the grid and the edge tile is returned
+    pub fn set(&mut self, x: usize, y: usize, tile: T) -> T {
+        match self.get_mut(x, y) {
+            Some(t) => std::mem::replace(t, tile),
+            None => self.edge.clone(),
+        }
+    }
+
+    pub fn set_signed(&mut self, x: isize, y: isize, tile: T) -> T {
+        match self.get_signed_mut(x, y) {
+            Some(t) => std::mem::replace(t, tile),
+            None => self.edge.clone(),
+        }
+    }
+
+    pub fn set_point(&mut self, p: Point, tile: T) -> T {
+        self.set_signed(p.x, p.y, tile)
+    }
+}
+
+impl<T: Clone> GridAccess<T> for Grid<T> {
+    fn get_point(&self, p: Point) -> &T {
+        Grid::get_point(self, p)
+    }
+
+    fn set_point(&mut self, p: Point, tile: T) -> T {
+        Grid::set_point(self, p, tile)
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        (self.width > 0 && self.height > 0).then(|| (Point::ZERO, Point::from((self.width - 1, self.height - 1))))
+    }
+}
+
+impl<T: Tile> Grid<T> {
+    ///parse a square text where each char is a tile (see Tile)
+    pub fn parse_tiles(text: &str, edge: T) -> Result<Self, AocError> {
+        Self::parse_with(text, edge, |_, _, c| T::try_from(c))
+    }
+}
+
+///one line per row, without a line delimiter after the last one (the output of a char grid can be parsed back)
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for t in row {
+                write!(f, "{t}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use proc_macros::Tile;
+
+    #[derive(Tile, Copy, Clone, Debug, Eq, PartialEq)]
+    enum Cell {
+        #[tile('.')]
+        Empty,
+        #[tile('#', 'X')]
+        Wall,
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::parse("ab\r\ncd\r\nef", ' ', Some).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!((grid.get(1, 2), grid.get(2, 0), grid.get_signed(-1, 0)), (&'f', &' ', &' '));
+        assert_eq!(grid.rows().map(|r| r.iter().collect::<String>()).collect::<Vec<String>>(), vec!["ab", "cd", "ef"]);
+        assert_eq!(grid.columns().map(|c| c.collect::<String>()).collect::<Vec<String>>(), vec!["ace", "bdf"]);
+        assert_eq!(grid.to_string(), "ab\ncd\nef");
+        let mut digits = Grid::parse("12\n34", 0, |c| c.to_digit(10)).unwrap();
+        assert_eq!((digits.set(0, 1, 9), digits.set_signed(0, -1, 9)), (3, 0));
+        assert_eq!(digits.to_string(), "12\n94");
+    }
+
+    #[test]
+    fn test_tiles() {
+        let grid = Grid::parse_tiles(".#\nX.", Cell::Wall).unwrap();
+        assert_eq!((grid.get(0, 0), grid.get(0, 1)), (&Cell::Empty, &Cell::Wall));
+        assert_eq!(grid.to_string(), ".#\n#.");
+        let error = Grid::<Cell>::parse_tiles(".#\n.o", Cell::Wall).unwrap_err();
+        assert_eq!((error.message.as_str(), error.location.map(|l| (l.line, l.column))), ("invalid Cell tile 'o'", Some((2, 2))));
+    }
+
+    #[test]
+    fn test_grid_errors() {
+        let error = Grid::parse("..\n.#\n..", '.', |c| (c == '.').then_some(c)).unwrap_err();
+        assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 2)));
+        let error = Grid::parse("..\n.é.\n..", '.', Some).unwrap_err();
+        assert_eq!((error.message.as_str(), error.location.map(|l| (l.line, l.column))), ("all lines should be 2 chars long", Some((2, 3))));
+    }
+}
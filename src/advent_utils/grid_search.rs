@@ -0,0 +1,125 @@
+use super::{Direction, Grid, Point, Tile, AocError};
+
+///transforms of a grid, they all return a new grid with the same edge tile
+impl<T: Clone> Grid<T> {
+    ///the tile at (x, y) moves to (y, x)
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height(), self.width(), self.edge().clone(), |x, y| self.get(y, x).clone())
+    }
+
+    ///quarter turn clockwise
+    pub fn rotate_right(&self) -> Self {
+        Self::from_fn(self.height(), self.width(), self.edge().clone(), |x, y| self.get(y, self.height() - 1 - x).clone())
+    }
+
+    ///quarter turn counter clockwise
+    pub fn rotate_left(&self) -> Self {
+        Self::from_fn(self.height(), self.width(), self.edge().clone(), |x, y| self.get(self.width() - 1 - y, x).clone())
+    }
+
+    ///mirror left and right
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width(), self.height(), self.edge().clone(), |x, y| self.get(self.width() - 1 - x, y).clone())
+    }
+
+    ///mirror top and bottom
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width(), self.height(), self.edge().clone(), |x, y| self.get(x, self.height() - 1 - y).clone())
+    }
+
+    ///the width x height part of the grid whose top left corner is (x, y), None if it does not fit in the grid
+    pub fn subgrid(&self, x: usize, y: usize, width: usize, height: usize) -> Option<Self> {
+        (x + width <= self.width() && y + height <= self.height()).then(|| Self::from_fn(width, height, self.edge().clone(), |sx, sy| self.get(x + sx, y + sy).clone()))
+    }
+
+    ///the distinct grids obtained by rotations and reflections (8 at most, less if the grid has symmetries)
+    pub fn symmetries(&self) -> Vec<Self> where T: PartialEq {
+        let mut out: Vec<Self> = Vec::new();
+        let mut current = self.clone();
+        for _ in 0..4 {
+            for g in [current.flip_horizontal(), current.clone()] {
+                if !out.contains(&g) {
+                    out.push(g);
+                }
+            }
+            current = current.rotate_right();
+        }
+        out
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    ///every occurrence of word read from a start position in one of the directions (a palindrome is found twice on a line)
+    pub fn find_word<D: Direction>(&self, word: &[T], directions: impl IntoIterator<Item = D>) -> Vec<(Point, D)> {
+        let directions: Vec<D> = directions.into_iter().collect();
+        let mut found = Vec::new();
+        for ((x, y), tile) in self.iter() {
+            if word.first() != Some(tile) {
+                continue;
+            }
+            let start = Point::from((x, y));
+            for &d in &directions {
+                let step = Point::from(d.delta());
+                if word.iter().enumerate().skip(1).all(|(i, w)| self.contains_point(start + step * i as isize) && self.get_point(start + step * i as isize) == w) {
+                    found.push((start, d));
+                }
+            }
+        }
+        found
+    }
+
+    ///top left corners of the places where the pattern matches, None tiles of the pattern match anything
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>) -> Vec<Point> {
+        let mut found = Vec::new();
+        for y in 0..(self.height() + 1).saturating_sub(pattern.height()) {
+            for x in 0..(self.width() + 1).saturating_sub(pattern.width()) {
+                if pattern.iter().all(|((px, py), t)| t.as_ref().is_none_or(|t| self.get(x + px, y + py) == t)) {
+                    found.push(Point::from((x, y)));
+                }
+            }
+        }
+        found
+    }
+
+    ///matches of the pattern under all its rotations and reflections, a place is found once for each distinct orientation matching it
+    pub fn find_pattern_symmetries(&self, pattern: &Grid<Option<T>>) -> Vec<Point> where T: Clone {
+        pattern.symmetries().iter().flat_map(|p| self.find_pattern(p)).collect()
+    }
+}
+
+impl<T: Tile> Grid<Option<T>> {
+    ///parse a pattern for find_pattern, the wildcard char matches any tile
+    pub fn parse_pattern(text: &str, wildcard: char) -> Result<Self, AocError> {
+        Self::parse_with(text, None, |_, _, c| if c == wildcard { Ok(None) } else { T::try_from(c).map(Some) })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::advent_utils::{Dir4, Dir8};
+
+    #[test]
+    fn test_transforms() {
+        let grid = Grid::parse("abc\ndef", ' ', Some).unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!((grid.flip_horizontal().to_string(), grid.flip_vertical().to_string()), ("cba\nfed".to_string(), "def\nabc".to_string()));
+        assert_eq!(grid.subgrid(1, 0, 2, 2).map(|g| g.to_string()), Some("bc\nef".to_string()));
+        assert_eq!(grid.subgrid(2, 0, 2, 2), None);
+        assert_eq!(grid.symmetries().len(), 8);
+        assert_eq!(Grid::parse("ab\nba", ' ', Some).unwrap().symmetries().len(), 2);
+    }
+
+    #[test]
+    fn test_search() {
+        let grid = Grid::parse("abcb\nbxbx\ncbab", ' ', Some).unwrap();
+        assert_eq!(grid.find_word(&['a', 'b', 'c'], Dir4::iter()), vec![(Point::new(0, 0), Dir4::Right), (Point::new(0, 0), Dir4::Down), (Point::new(2, 2), Dir4::Up), (Point::new(2, 2), Dir4::Left)]);
+        assert_eq!(grid.find_word(&['a', 'x'], Dir8::iter()).len(), 3);
+        let pattern = Grid::parse("b?\n?b", None, |c| Some((c != '?').then_some(c))).unwrap();
+        assert_eq!(grid.find_pattern(&pattern), vec![Point::new(1, 0), Point::new(0, 1), Point::new(2, 1)]);
+        assert_eq!(grid.find_pattern_symmetries(&pattern).len(), 6);
+    }
+}
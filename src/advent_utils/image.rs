@@ -0,0 +1,195 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use super::GridAccess;
+
+pub type Rgb = [u8; 3];
+
+///rgb image, used to dump grids and simulation frames (saved as PPM or PNG)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self { width, height, pixels: vec![background; width * height] }
+    }
+
+    ///render the bounding box of a grid, each tile is a scale x scale square of the color given by color
+    pub fn from_grid<T>(grid: &impl GridAccess<T>, scale: usize, color: impl Fn(&T) -> Rgb) -> Self {
+        let Some((min, max)) = grid.bounds() else { return Self::new(0, 0, [0; 3]) };
+        let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+        let mut image = Self::new(width * scale, height * scale, [0; 3]);
+        for y in 0..height {
+            for x in 0..width {
+                let c = color(grid.get_point(min + (x, y).into()));
+                for dy in 0..scale {
+                    let row = (y * scale + dy) * image.width;
+                    image.pixels[row + x * scale..row + (x + 1) * scale].fill(c);
+                }
+            }
+        }
+        image
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[x + y * self.width]
+    }
+
+    ///copy an image with its top left corner at (x, y), the parts outside of this image are cut
+    pub fn draw(&mut self, image: &Image, x: usize, y: usize) {
+        for sy in 0..image.height.min(self.height.saturating_sub(y)) {
+            let width = image.width.min(self.width.saturating_sub(x));
+            let row = (y + sy) * self.width + x;
+            self.pixels[row..row + width].copy_from_slice(&image.pixels[sy * image.width..sy * image.width + width]);
+        }
+    }
+
+    ///binary PPM (P6)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().flatten());
+        out
+    }
+
+    ///8 bits rgb PNG, the data is not compressed (stored deflate blocks) to keep the encoder small
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0); //no filter
+            raw.extend(row.iter().flatten());
+        }
+        let mut zlib = vec![0x78, 0x01];
+        let blocks: Vec<&[u8]> = if raw.is_empty() { vec![&[]] } else { raw.chunks(65535).collect() };
+        for (i, block) in blocks.iter().enumerate() {
+            zlib.push((i + 1 == blocks.len()) as u8);
+            zlib.extend((block.len() as u16).to_le_bytes());
+            zlib.extend((!(block.len() as u16)).to_le_bytes());
+            zlib.extend(*block);
+        }
+        zlib.extend(adler32(&raw).to_be_bytes());
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]); //8 bits per channel, rgb, deflate, no filter, no interlace
+        let mut out = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        for (kind, data) in [(b"IHDR", header.as_slice()), (b"IDAT", zlib.as_slice()), (b"IEND", &[])] {
+            out.extend((data.len() as u32).to_be_bytes());
+            let start = out.len();
+            out.extend(kind);
+            out.extend(data);
+            let crc = crc32(&out[start..]);
+            out.extend(crc.to_be_bytes());
+        }
+        out
+    }
+
+    ///write the image as PNG if the path ends with .png, as PPM otherwise
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, if path.extension().is_some_and(|e| e == "png") { self.to_png() } else { self.to_ppm() })
+    }
+
+    ///tile frames (of the size of the first one) in rows of columns frames separated by margin pixels of background
+    pub fn contact_sheet(frames: &[Image], columns: usize, margin: usize, background: Rgb) -> Self {
+        let (width, height) = frames.first().map_or((0, 0), |f| (f.width, f.height));
+        let columns = columns.clamp(1, frames.len().max(1));
+        let rows = frames.len().div_ceil(columns);
+        let mut sheet = Self::new(columns * (width + margin) + margin, rows * (height + margin) + margin, background);
+        for (i, frame) in frames.iter().enumerate() {
+            sheet.draw(frame, margin + (i % columns) * (width + margin), margin + (i / columns) * (height + margin));
+        }
+        sheet
+    }
+}
+
+///writes the frames of a simulation as numbered files: {dir}/{prefix}_00000.{extension}, {dir}/{prefix}_00001.{extension}, ...
+pub struct FrameWriter {
+    pub dir: PathBuf,
+    pub prefix: String,
+    pub extension: String,
+    pub next: usize,
+}
+
+impl FrameWriter {
+    ///extension is png or ppm
+    pub fn new(dir: impl Into<PathBuf>, prefix: &str, extension: &str) -> Self {
+        Self { dir: dir.into(), prefix: prefix.to_string(), extension: extension.to_string(), next: 0 }
+    }
+
+    pub fn write(&mut self, frame: &Image) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!("{}_{:05}.{}", self.prefix, self.next, self.extension));
+        frame.save(&path)?;
+        self.next += 1;
+        Ok(path)
+    }
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, b| {
+        (0..8).fold(crc ^ *b as u32, |crc, _| if crc & 1 == 1 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 })
+    })
+}
+
+pub fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), d| {
+        let a = (a + *d as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::advent_utils::{Grid, SparseGrid, Point};
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+    }
+
+    #[test]
+    fn test_encoders() {
+        let grid = Grid::parse("#.\n.#", ' ', Some).unwrap();
+        let image = Image::from_grid(&grid, 2, |c| if *c == '#' { [255, 0, 0] } else { [0, 0, 0] });
+        assert_eq!((image.width, image.height, image.get(1, 1), image.get(2, 1)), (4, 4, [255, 0, 0], [0, 0, 0]));
+        assert_eq!(&image.to_ppm()[..11], b"P6\n4 4\n255\n");
+        assert_eq!(image.to_ppm().len(), 11 + 4 * 4 * 3);
+
+        let png = image.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        //walk the chunks: each crc must be valid and the stored blocks must give back the scanlines
+        let (mut i, mut data) = (8, Vec::<u8>::new());
+        while i < png.len() {
+            let len = u32::from_be_bytes(png[i..i + 4].try_into().unwrap()) as usize;
+            assert_eq!(crc32(&png[i + 4..i + 8 + len]).to_be_bytes(), png[i + 8 + len..i + 12 + len]);
+            if &png[i + 4..i + 8] == b"IDAT" {
+                data.extend(&png[i + 8 + 7..i + 8 + len - 4]);
+            }
+            i += 12 + len;
+        }
+        assert_eq!(data.len(), 4 * (1 + 4 * 3));
+        assert_eq!(&data[..7], &[0, 255, 0, 0, 255, 0, 0]);
+    }
+
+    #[test]
+    fn test_contact_sheet() {
+        let mut sparse = SparseGrid::new(false);
+        sparse.insert(Point::new(-1, 0), true);
+        sparse.insert(Point::new(1, 2), true);
+        let frame = Image::from_grid(&sparse, 1, |t| if *t { [255; 3] } else { [0; 3] });
+        assert_eq!((frame.width, frame.height, frame.get(0, 0), frame.get(2, 2)), (3, 3, [255; 3], [255; 3]));
+        let sheet = Image::contact_sheet(&[frame.clone(), frame.clone(), frame], 2, 1, [9; 3]);
+        assert_eq!((sheet.width, sheet.height), (9, 9));
+        assert_eq!((sheet.get(0, 0), sheet.get(1, 1), sheet.get(5, 1), sheet.get(5, 5)), ([9; 3], [255; 3], [255; 3], [9; 3]));
+    }
+}
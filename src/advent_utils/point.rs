@@ -0,0 +1,149 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use super::{Dir4, Dir8, Direction};
+
+///signed position or vector, y grows downward like in the grids
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    pub fn step(self, d: impl Direction) -> Self {
+        let (dx, dy) = d.delta();
+        Self::new(self.x + dx, self.y + dy)
+    }
+
+    ///the 4 (or 8 with D = Dir8) adjacent points, clockwise starting from the one above
+    pub fn neighbors<D: Direction>(self) -> impl Iterator<Item = Self> {
+        D::iter().map(move |d| self.step(d))
+    }
+
+    ///the position as unsigned coordinates, None if it is outside of a width x height grid
+    pub fn to_usize(self, width: usize, height: usize) -> Option<(usize, usize)> {
+        let (x, y) = (usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?);
+        (x < width && y < height).then_some((x, y))
+    }
+
+    ///index of the position in a row by row width x height grid
+    pub fn grid_index(self, width: usize, height: usize) -> Option<usize> {
+        self.to_usize(width, height).map(|(x, y)| x + y * width)
+    }
+
+    ///bring the position back in a width x height toroidal map (leaving by one side enters by the other)
+    pub fn wrap(self, width: usize, height: usize) -> Self {
+        Self::new(self.x.rem_euclid(width as isize), self.y.rem_euclid(height as isize))
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+impl From<Dir4> for Point {
+    fn from(d: Dir4) -> Self {
+        d.delta().into()
+    }
+}
+
+impl From<Dir8> for Point {
+    fn from(d: Dir8) -> Self {
+        d.delta().into()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 4));
+        assert_eq!((a + b, a - b, -a * 3), (Point::new(-2, 2), Point::new(4, -6), Point::new(-3, 6)));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (10, 6));
+        assert_eq!(a.step(Dir8::DownLeft), a + Dir8::DownLeft.into());
+        assert_eq!(Point::ZERO.neighbors::<Dir4>().collect::<Vec<Point>>(), vec![Point::new(0, -1), Point::new(1, 0), Point::new(0, 1), Point::new(-1, 0)]);
+        assert_eq!(Point::ZERO.neighbors::<Dir8>().count(), 8);
+    }
+
+    #[test]
+    fn test_grid_conversions() {
+        assert_eq!(Point::new(2, 1).to_usize(3, 2), Some((2, 1)));
+        assert_eq!(Point::new(2, 1).grid_index(3, 2), Some(5));
+        assert_eq!((Point::new(3, 1).grid_index(3, 2), Point::new(0, -1).grid_index(3, 2)), (None, None));
+        assert_eq!(Point::new(-1, 7).wrap(11, 7), Point::new(10, 0));
+    }
+}
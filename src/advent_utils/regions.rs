@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+use super::{Dir4, Dir8, Direction, Grid, Point};
+
+///connected component of a grid, points are in the order they were reached from the first one
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Region {
+    pub points: Vec<Point>,
+    ///number of sides of the tiles that touch another region or the edge of the grid
+    pub perimeter: usize,
+    ///number of straight fences around the region (holes included), it is also its number of corners
+    pub sides: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+}
+
+///result of Grid::regions, labels gives the index of the region of each tile (usize::MAX outside of the grid)
+#[derive(Clone, Debug)]
+pub struct Regions {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    pub fn region_at(&self, p: Point) -> Option<&Region> {
+        self.regions.get(*self.labels.get_point(p))
+    }
+}
+
+impl<T> Grid<T> {
+    ///points reachable from start moving up, right, down or left, connected(from, to) tells if a move between adjacent tiles is allowed
+    ///the start comes first and the points are sorted by distance from it
+    pub fn flood(&self, start: Point, connected: impl Fn(&T, &T) -> bool) -> Vec<Point> {
+        let mut seen = Grid::new(self.width(), self.height(), false, true);
+        self.flood_into(start, &connected, &mut seen)
+    }
+
+    fn flood_into(&self, start: Point, connected: &impl Fn(&T, &T) -> bool, seen: &mut Grid<bool>) -> Vec<Point> {
+        if seen.set_point(start, true) {
+            return Vec::new();
+        }
+        let mut points = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(p) = queue.pop_front() {
+            for n in p.neighbors::<Dir4>() {
+                if self.contains_point(n) && connected(self.get_point(p), self.get_point(n)) && !seen.set_point(n, true) {
+                    points.push(n);
+                    queue.push_back(n);
+                }
+            }
+        }
+        points
+    }
+
+    ///split the grid in 4-connected regions of tiles where same(a, b) is true for adjacent tiles (same should be an equivalence)
+    pub fn regions(&self, same: impl Fn(&T, &T) -> bool) -> Regions {
+        let mut seen = Grid::new(self.width(), self.height(), false, true);
+        let mut labels = Grid::new(self.width(), self.height(), usize::MAX, usize::MAX);
+        let mut regions = Vec::new();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let points = self.flood_into(Point::from((x, y)), &same, &mut seen);
+                if points.is_empty() {
+                    continue;
+                }
+                for p in &points {
+                    labels.set_point(*p, regions.len());
+                }
+                regions.push(Region { points, perimeter: 0, sides: 0 });
+            }
+        }
+        for (label, region) in regions.iter_mut().enumerate() {
+            let inside = |p: Point| *labels.get_point(p) == label;
+            for p in &region.points {
+                region.perimeter += p.neighbors::<Dir4>().filter(|n| !inside(*n)).count();
+                //a corner is either convex (both sides outside) or concave (both sides inside but not the diagonal between them)
+                region.sides += Dir4::iter().filter(|d| {
+                    let (a, b) = (inside(p.step(*d)), inside(p.step(d.turn_right())));
+                    let diagonal = inside(p.step(Dir8::from(*d).turn_right()));
+                    (!a && !b) || (a && b && !diagonal)
+                }).count();
+            }
+        }
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    fn prices(text: &str) -> (usize, usize) {
+        let regions = Grid::parse(text, ' ', Some).unwrap().regions(|a, b| a == b).regions;
+        (regions.iter().map(|r| r.area() * r.perimeter).sum(), regions.iter().map(|r| r.area() * r.sides).sum())
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC", ' ', Some).unwrap();
+        let regions = grid.regions(|a, b| a == b);
+        let summary: Vec<(char, usize, usize, usize)> = regions.regions.iter().map(|r| (*grid.get_point(r.points[0]), r.area(), r.perimeter, r.sides)).collect();
+        assert_eq!(summary, vec![('A', 4, 10, 4), ('B', 4, 8, 4), ('C', 4, 10, 8), ('D', 1, 4, 4), ('E', 3, 8, 4)]);
+        assert_eq!(regions.region_at(Point::new(3, 3)).map(|r| r.area()), Some(4));
+        assert_eq!(prices("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO"), (772, 436));
+        assert_eq!(prices("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA").1, 368);
+    }
+
+    #[test]
+    fn test_flood() {
+        let grid = Grid::parse("0123\n1234\n8765\n9876", 0, |c| c.to_digit(10)).unwrap();
+        let reachable = grid.flood(Point::new(0, 0), |from, to| *to == from + 1);
+        assert_eq!(reachable.len(), 16);
+        assert_eq!(grid.flood(Point::new(0, 3), |from, to| *to == from + 1), vec![Point::new(0, 3)]);
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use super::{iter_lines, GridAccess, Point};
+
+fn extend(bounds: Option<(Point, Point)>, p: Point) -> Option<(Point, Point)> {
+    Some(match bounds {
+        Some((min, max)) => (Point::new(min.x.min(p.x), min.y.min(p.y)), Point::new(max.x.max(p.x), max.y.max(p.y))),
+        None => (p, p),
+    })
+}
+
+///unbounded grid storing only the tiles that were set, for maps that grow or are mostly empty
+///the bounding box grows with the tiles that are set and shrinks when a tile on its border is removed
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    tiles: HashMap<Point, T>,
+    empty: T,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(empty: T) -> Self {
+        Self { tiles: HashMap::new(), empty, bounds: None }
+    }
+
+    ///read a text line by line (lines can have any length), f gives the tile of a char or None to leave the position empty
+    pub fn parse(text: &str, empty: T, mut f: impl FnMut(char) -> Option<T>) -> Self {
+        let mut grid = Self::new(empty);
+        for (y, line) in iter_lines(text, true) {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(tile) = f(c) {
+                    grid.insert(Point::from((x, y)), tile);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.tiles.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.tiles.get_mut(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, tile: T) -> Option<T> {
+        self.bounds = extend(self.bounds, p);
+        self.tiles.insert(p, tile)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        let tile = self.tiles.remove(&p)?;
+        if self.bounds.is_some_and(|(min, max)| p.x == min.x || p.y == min.y || p.x == max.x || p.y == max.y) {
+            self.bounds = self.tiles.keys().fold(None, |bounds, p| extend(bounds, *p));
+        }
+        Some(tile)
+    }
+
+    ///the stored tiles in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.tiles.iter().map(|(p, t)| (*p, t))
+    }
+}
+
+impl<T: Clone> GridAccess<T> for SparseGrid<T> {
+    fn get_point(&self, p: Point) -> &T {
+        self.tiles.get(&p).unwrap_or(&self.empty)
+    }
+
+    fn set_point(&mut self, p: Point, tile: T) -> T {
+        self.insert(p, tile).unwrap_or_else(|| self.empty.clone())
+    }
+
+    fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+}
+
+impl<T: Clone + Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::advent_utils::Grid;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::parse("a..\n..b\n", '.', |c| (c != '.').then_some(c));
+        assert_eq!((grid.len(), grid.bounds()), (2, Some((Point::new(0, 0), Point::new(2, 1)))));
+        assert_eq!(grid.set_point(Point::new(-1, 3), 'c'), '.');
+        assert_eq!(grid.to_string(), ".a..\n...b\n....\nc...");
+        grid.remove(Point::new(-1, 3));
+        grid.remove(Point::new(0, 0));
+        assert_eq!(grid.bounds(), Some((Point::new(2, 1), Point::new(2, 1))));
+        assert_eq!((*grid.get_point(Point::new(2, 1)), *grid.get_point(Point::new(9, 9))), ('b', '.'));
+    }
+
+    #[test]
+    fn test_shared_access() {
+        fn count<T: PartialEq>(grid: &impl GridAccess<T>, tile: T) -> usize {
+            grid.bounds().map_or(0, |(min, max)| (min.y..=max.y).flat_map(|y| (min.x..=max.x).map(move |x| Point::new(x, y))).filter(|p| *grid.get_point(*p) == tile).count())
+        }
+        let dense = Grid::parse("#.#\n.#.", ' ', Some).unwrap();
+        let sparse = SparseGrid::parse("#.#\n.#.", ' ', |c| (c == '#').then_some(c));
+        assert_eq!((count(&dense, '#'), count(&sparse, '#')), (3, 3));
+        assert_eq!(dense.render(), dense.to_string());
+    }
+}
@@ -0,0 +1,244 @@
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+use super::{GridAccess, Point, Rgb};
+
+///a char of the terminal and its color (None keeps the default color of the terminal)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub c: char,
+    pub color: Option<Rgb>,
+}
+
+impl Cell {
+    pub fn new(c: char, color: Option<Rgb>) -> Self {
+        Self { c, color }
+    }
+}
+
+///commands of the user, read from stdin one per line (the terminal is not in raw mode, so each command is followed by enter)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    ///empty line: show the next frame (and pause if playing)
+    Step,
+    ///p: pause or resume
+    Pause,
+    ///+ or -: double or halve the frame rate
+    Faster,
+    Slower,
+    ///q: stop rendering, the simulation can keep running
+    Quit,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "" => Some(Command::Step),
+            "p" => Some(Command::Pause),
+            "+" => Some(Command::Faster),
+            "-" => Some(Command::Slower),
+            "q" => Some(Command::Quit),
+            _ => None,
+        }
+    }
+}
+
+///spawn a thread reading the commands typed on stdin
+pub fn stdin_commands() -> Receiver<Command> {
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            if let Some(command) = Command::parse(&line) && sender.send(command).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+///draws the frames of a simulation in an ANSI terminal, only the cells that changed since the previous frame are redrawn
+///frames are shown at most fps times per second, without commands the animation plays until the end
+pub struct Animation<W: Write> {
+    out: W,
+    frame: Duration,
+    commands: Option<Receiver<Command>>,
+    paused: bool,
+    quit: bool,
+    last: Option<Instant>,
+    width: usize,
+    screen: Vec<Cell>,
+}
+
+impl<W: Write> Animation<W> {
+    pub fn new(out: W, fps: f64) -> Self {
+        Self {
+            out,
+            frame: Duration::from_secs_f64(1. / fps.max(1e-3)),
+            commands: None,
+            paused: false,
+            quit: false,
+            last: None,
+            width: 0,
+            screen: Vec::new(),
+        }
+    }
+
+    ///control the animation with commands (see stdin_commands), it then starts paused and waits for a step or pause command
+    pub fn with_commands(mut self, commands: Receiver<Command>) -> Self {
+        self.commands = Some(commands);
+        self.paused = true;
+        self
+    }
+
+    ///false once the user quit, the frames are then ignored
+    pub fn is_running(&self) -> bool {
+        !self.quit
+    }
+
+    fn apply(&mut self, command: Command) {
+        match command {
+            Command::Step => self.paused = true,
+            Command::Pause => self.paused = !self.paused,
+            Command::Faster => self.frame /= 2,
+            Command::Slower => self.frame *= 2,
+            Command::Quit => self.quit = true,
+        }
+    }
+
+    ///wait for the time of the next frame, or for a command while paused
+    fn wait(&mut self) {
+        loop {
+            let command = match &self.commands {
+                Some(commands) if self.paused => match commands.recv() {
+                    Ok(command) => Some(command),
+                    Err(_) => {
+                        self.commands = None;
+                        self.paused = false;
+                        None
+                    }
+                },
+                Some(commands) => match commands.try_recv() {
+                    Ok(command) => Some(command),
+                    Err(TryRecvError::Empty) => None,
+                    Err(TryRecvError::Disconnected) => {
+                        self.commands = None;
+                        None
+                    }
+                },
+                None => None,
+            };
+            match command {
+                //a step while paused shows one frame, while playing it pauses after this frame
+                Some(Command::Step) => {
+                    self.paused = true;
+                    return;
+                }
+                Some(command) => {
+                    self.apply(command);
+                    if self.quit {
+                        return;
+                    }
+                }
+                None => break,
+            }
+        }
+        if let Some(last) = self.last {
+            thread::sleep(self.frame.saturating_sub(last.elapsed()));
+        }
+    }
+
+    ///show a width x height frame where cell gives the content of each position
+    pub fn render(&mut self, width: usize, height: usize, cell: impl Fn(usize, usize) -> Cell) -> io::Result<bool> {
+        self.wait();
+        if self.quit {
+            return Ok(false);
+        }
+        let mut out = String::new();
+        let full = width != self.width || width * height != self.screen.len();
+        if full {
+            out.push_str("\x1b[2J");
+            self.width = width;
+            self.screen = vec![Cell::new(' ', None); width * height];
+        }
+        let mut color = None;
+        for y in 0..height {
+            let mut cursor = false;
+            for x in 0..width {
+                let c = cell(x, y);
+                if !full && self.screen[x + y * width] == c {
+                    cursor = false;
+                    continue;
+                }
+                self.screen[x + y * width] = c;
+                if !cursor {
+                    out.push_str(&format!("\x1b[{};{}H", y + 1, x + 1));
+                    cursor = true;
+                }
+                if c.color != color {
+                    match c.color {
+                        Some([r, g, b]) => out.push_str(&format!("\x1b[38;2;{r};{g};{b}m")),
+                        None => out.push_str("\x1b[0m"),
+                    }
+                    color = c.color;
+                }
+                out.push(c.c);
+            }
+        }
+        if color.is_some() {
+            out.push_str("\x1b[0m");
+        }
+        //leave the cursor under the frame so that what the simulation prints does not overwrite it
+        out.push_str(&format!("\x1b[{};1H", height + 1));
+        self.out.write_all(out.as_bytes())?;
+        self.out.flush()?;
+        self.last = Some(Instant::now());
+        Ok(true)
+    }
+
+    ///show the bounding box of a grid, style gives the cell of each tile
+    pub fn render_grid<T>(&mut self, grid: &impl GridAccess<T>, style: impl Fn(&T) -> Cell) -> io::Result<bool> {
+        let Some((min, max)) = grid.bounds() else { return self.render(0, 0, |_, _| Cell::new(' ', None)) };
+        self.render((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize, |x, y| style(grid.get_point(min + Point::from((x, y)))))
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::advent_utils::Grid;
+
+    fn style(c: &char) -> Cell {
+        Cell::new(*c, (*c == '#').then_some([255, 0, 0]))
+    }
+
+    #[test]
+    fn test_redraw_changed_cells() {
+        let mut animation = Animation::new(Vec::new(), 1000.);
+        let mut grid = Grid::parse("#.\n..", ' ', Some).unwrap();
+        animation.render_grid(&grid, style).unwrap();
+        grid.set(1, 1, 'o');
+        animation.render_grid(&grid, style).unwrap();
+        let out = String::from_utf8(animation.into_inner()).unwrap();
+        let (first, second) = out.split_at(out.find("\x1b[3;1H").unwrap() + 6);
+        assert_eq!(first, "\x1b[2J\x1b[1;1H\x1b[38;2;255;0;0m#\x1b[0m.\x1b[2;1H..\x1b[3;1H");
+        assert_eq!(second, "\x1b[2;2Ho\x1b[3;1H");
+    }
+
+    #[test]
+    fn test_commands() {
+        let (sender, receiver) = channel();
+        let mut animation = Animation::new(Vec::new(), 1000.).with_commands(receiver);
+        for command in [Command::Step, Command::Pause, Command::Quit] {
+            sender.send(command).unwrap();
+        }
+        assert!(animation.render(1, 1, |_, _| Cell::new('a', None)).unwrap());
+        assert!(animation.paused);
+        assert!(!animation.render(1, 1, |_, _| Cell::new('b', None)).unwrap());
+        assert_eq!(Command::parse(" p "), Some(Command::Pause));
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use crate::profile::Ctx;
+use crate::runner::{selected_variants, Day};
+
+///how long a measure lasts: the function is first run during warmup (at least once) to estimate its duration,
+///then the number of runs is chosen to last about target, clamped between min_runs and max_runs
+#[derive(Copy, Clone, Debug)]
+pub struct BenchConfig {
+    pub warmup: Duration,
+    pub target: Duration,
+    pub min_runs: usize,
+    pub max_runs: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(300),
+            target: Duration::from_secs(2),
+            min_runs: 5,
+            max_runs: 100_000,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let runs = samples.len().max(1);
+        let at = |q: f64| samples.get(((runs - 1) as f64 * q).round() as usize).copied().unwrap_or_default();
+        let mean = samples.iter().map(|s| s.as_secs_f64()).sum::<f64>() / runs as f64;
+        let variance = samples.iter().map(|s| (s.as_secs_f64() - mean).powi(2)).sum::<f64>() / runs as f64;
+        Self {
+            runs: samples.len(),
+            min: at(0.),
+            median: at(0.5),
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            p95: at(0.95),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "median {:?} (min {:?}, mean {:?}, σ {:?}, p95 {:?}, {} runs)", self.median, self.min, self.mean, self.std_dev, self.p95, self.runs)
+    }
+}
+
+///run f repeatedly (see BenchConfig) and compute the statistics of its durations
+pub fn measure<R>(config: &BenchConfig, mut f: impl FnMut() -> R) -> Stats {
+    let start = Instant::now();
+    let mut warmup_runs = 0;
+    while warmup_runs == 0 || start.elapsed() < config.warmup {
+        black_box(f());
+        warmup_runs += 1;
+    }
+    let estimate = start.elapsed().as_secs_f64() / warmup_runs as f64;
+    let runs = ((config.target.as_secs_f64() / estimate.max(1e-9)) as usize).clamp(config.min_runs, config.max_runs);
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+    }
+    Stats::from_samples(samples)
+}
+
+///measure the parse function of a day and the selected variants of both parts (all of them if variant is None), selected like in Day::run
+///results are named {year}/{day}/parse and {year}/{day}/{part}/{variant}
+pub fn bench_day(day: &Day, input: &str, variant: Option<&str>, config: &BenchConfig) -> Result<Vec<(String, Stats)>, String> {
+    day.check_variant(variant)?;
+    let mut results = Vec::new();
+    let (parsed, _) = day.parse_input(input)?;
+    let ctx = Ctx::disabled();
+    if let Some(parse) = day.parse {
+        results.push((format!("{}/{}/parse", day.year, day.day), measure(config, || parse(input))));
+    }
+    for (part, variants) in [("a", day.a), ("b", day.b)] {
+        for v in selected_variants(variants, variant) {
+            (v.run)(input, parsed.as_ref(), &ctx).map_err(|e| format!("part {part}: {} variant failed: {e}", v.name))?;
+            results.push((format!("{}/{}/{part}/{}", day.year, day.day, v.name), measure(config, || (v.run)(input, parsed.as_ref(), &ctx))));
+        }
+    }
+    Ok(results)
+}
+
+///stored results of previous benchmarks, one line per result: name runs min median mean std_dev p95 (durations in nanoseconds)
+#[derive(Default)]
+pub struct Baseline {
+    pub results: BTreeMap<String, Stats>,
+}
+
+impl Baseline {
+    ///a missing file is an empty baseline, malformed lines are ignored
+    pub fn load(path: &str) -> Self {
+        let mut baseline = Self::default();
+        for line in fs::read_to_string(path).unwrap_or_default().lines() {
+            let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+            if let [name, values @ ..] = fields.as_slice() && let Some([runs, min, median, mean, std_dev, p95]) = values.iter().map(|v| v.parse::<u64>().ok()).collect::<Option<Vec<u64>>>().and_then(|v| <[u64; 6]>::try_from(v).ok()) {
+                let ns = Duration::from_nanos;
+                baseline.results.insert(name.to_string(), Stats { runs: runs as usize, min: ns(min), median: ns(median), mean: ns(mean), std_dev: ns(std_dev), p95: ns(p95) });
+            }
+        }
+        baseline
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        fs::write(path, self.results.iter().map(|(name, s)| {
+            format!("{name} {} {} {} {} {} {}\n", s.runs, s.min.as_nanos(), s.median.as_nanos(), s.mean.as_nanos(), s.std_dev.as_nanos(), s.p95.as_nanos())
+        }).collect::<String>())
+    }
+
+    ///relative change of the median compared to the baseline (0.1 is 10% slower), None if there is no baseline for this name
+    pub fn change(&self, name: &str, stats: &Stats) -> Option<f64> {
+        self.results.get(name).map(|b| stats.median.as_secs_f64() / b.median.as_secs_f64().max(1e-9) - 1.)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples((1..=20).rev().map(Duration::from_millis).collect());
+        assert_eq!((stats.runs, stats.min, stats.median, stats.p95), (20, Duration::from_millis(1), Duration::from_millis(11), Duration::from_millis(19)));
+        assert_eq!(stats.mean, Duration::from_micros(10500));
+        assert!((stats.std_dev.as_secs_f64() - 0.005766).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_baseline_change() {
+        let mut baseline = Baseline::default();
+        let stats = Stats::from_samples(vec![Duration::from_millis(10)]);
+        baseline.results.insert("2024/1/a/main".to_string(), stats);
+        let slower = Stats::from_samples(vec![Duration::from_millis(12)]);
+        assert!(baseline.change("2024/1/a/main", &slower).is_some_and(|c| (c - 0.2).abs() < 1e-9));
+        assert_eq!(baseline.change("2024/1/b/main", &slower), None);
+    }
+
+    #[test]
+    fn test_bench_variant_selection() {
+        let config = BenchConfig { warmup: Duration::ZERO, target: Duration::ZERO, min_runs: 1, max_runs: 1 };
+        let day = crate::runner::find_day(2024, 5).unwrap();
+        let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/inputs/2024/examples/5_1.txt"));
+        let names: Vec<String> = bench_day(day, input, Some("sort"), &config).unwrap().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["2024/5/parse", "2024/5/a/main", "2024/5/b/sort"]);
+        assert_eq!(bench_day(day, input, Some("nope"), &config).unwrap_err(), "no variant named nope");
+    }
+}
@@ -1,32 +1,36 @@
-fn parse(input: &str) -> (Vec<u32>, Vec<u32>) {
-    input.split("\n")
+use crate::advent_utils::AocError;
+use crate::generators::Rng;
+
+pub fn parse(input: &str) -> Result<(Vec<u32>, Vec<u32>), AocError> {
+    Ok(input.split("\n")
         .fold((Vec::new(), Vec::new()), |(mut lv, mut rv), line| {
             let mut line = line.split_ascii_whitespace();
-            match (line.next().and_then(|l| l.parse::<u32>().ok()), line.next().and_then(|r| r.parse::<u32>().ok())) {
-                (Some(l), Some(r)) => {
-                    lv.push(l);
-                    rv.push(r);
-                }
-                _ => {}
+            if let (Some(l), Some(r)) = (line.next().and_then(|l| l.parse::<u32>().ok()), line.next().and_then(|r| r.parse::<u32>().ok())) {
+                lv.push(l);
+                rv.push(r);
             }
             (lv, rv)
-        })
+        }))
 }
 
-pub fn y2024d1a(input: &str) -> Result<String, String> {
-    let (mut left, mut right) = parse(input);
+pub fn y2024d1a((left, right): &(Vec<u32>, Vec<u32>)) -> Result<u32, AocError> {
+    let (mut left, mut right) = (left.clone(), right.clone());
     left.sort();
     right.sort();
     let res = left.iter().zip(right.iter()).fold(0u32, |a, (l, r)| {
         a + l.abs_diff(*r)
     });
-    Ok(res.to_string())
+    Ok(res)
 }
 
-pub fn y2024d1b(input: &str) -> Result<String, String> {
-    let (left, right) = parse(input);
+pub fn y2024d1b((left, right): &(Vec<u32>, Vec<u32>)) -> Result<u32, AocError> {
     let res = left.iter().fold(0, |total, l| {
         total + *l * right.iter().fold(0, |i, c| if *c == *l { i + 1 } else { i })
     });
-    Ok(res.to_string())
+    Ok(res)
+}
+
+//size lines of two location ids
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| format!("{}   {}", rng.range(10000..100000), rng.range(10000..100000))).collect::<Vec<String>>().join("\n")
 }
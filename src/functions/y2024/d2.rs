@@ -1,4 +1,7 @@
-fn check(report: &Vec<i32>) -> bool {
+use crate::advent_utils::AocError;
+use crate::generators::Rng;
+
+fn check(report: &[i32]) -> bool {
     if report.len() <= 1 { true } else {
         if report[0] < report[1] {
             for i in 0..report.len() - 1 {
@@ -18,14 +21,14 @@ fn check(report: &Vec<i32>) -> bool {
     }
 }
 
-pub fn y2024d2a(input: &str) -> Result<String, String> {
+pub fn y2024d2a(input: &str) -> Result<u32, AocError> {
     let res = input.split("\n").fold(0u32, |total, report| {
         if check(&report.split_ascii_whitespace().filter_map(|ds| ds.parse::<i32>().ok()).collect::<Vec<i32>>()) { total + 1 } else { total }
     });
-    Ok(res.to_string())
+    Ok(res)
 }
 
-pub fn y2024d2b(input: &str) -> Result<String, String> {
+pub fn y2024d2b(input: &str) -> Result<u32, AocError> {
     let res = input.split("\n").fold(0u32, |total, report| {
         let report = report.split_ascii_whitespace().filter_map(|ds| ds.parse::<i32>().ok()).collect::<Vec<i32>>();
         if check(&report) { return total + 1; }
@@ -36,5 +39,20 @@ pub fn y2024d2b(input: &str) -> Result<String, String> {
         }
         total
     });
-    Ok(res.to_string())
+    Ok(res)
+}
+
+//size reports of 5 to 8 levels, mostly safe with a few bad levels
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| {
+        let increasing = rng.chance(0.5);
+        let mut level = rng.range(20..80) as i64;
+        let mut report = Vec::new();
+        for _ in 0..rng.range(5..9) {
+            report.push(level.to_string());
+            let step = if rng.chance(0.1) { rng.range(0..6) as i64 } else { rng.range(1..4) as i64 };
+            level += if increasing == rng.chance(0.95) { step } else { -step };
+        }
+        report.join(" ")
+    }).collect::<Vec<String>>().join("\n")
 }
@@ -1,7 +1,8 @@
 use regex::Regex;
+use crate::advent_utils::AocError;
 
-pub fn y2024d3a(input: &str) -> Result<String, String> {
-    let regex = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)").map_err(|e| format!("{e:?}"))?;
+pub fn y2024d3a(input: &str) -> Result<i32, AocError> {
+    let regex = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)")?;
     let res = regex.captures_iter(input).fold(0, |total, cap| {
         let [left, right] = cap.extract().1;
         match (left.parse::<i32>(), right.parse::<i32>()) {
@@ -9,11 +10,11 @@ pub fn y2024d3a(input: &str) -> Result<String, String> {
             _ => total
         }
     });
-    Ok(res.to_string())
+    Ok(res)
 }
 
-pub fn y2024d3b(input: &str) -> Result<String, String> {
-    let regex = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)|(do\(\))|(don't\(\))").map_err(|e| format!("{e:?}"))?;
+pub fn y2024d3b(input: &str) -> Result<i32, AocError> {
+    let regex = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)|(do\(\))|(don't\(\))")?;
     let mut toggle = true;
     let res = regex.captures_iter(input).fold(0, |total, cap| {
         if cap.get(3).is_some() {
@@ -31,5 +32,5 @@ pub fn y2024d3b(input: &str) -> Result<String, String> {
             total
         }
     });
-    Ok(res.to_string())
+    Ok(res)
 }
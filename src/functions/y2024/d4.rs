@@ -1,167 +1,73 @@
-pub enum XmasDirection {
-    Right,
-    BottomRight,
-    Bottom,
-    BottomLeft,
-    Left,
-    TopLeft,
-    Top,
-    TopRight
-}
-
-impl XmasDirection {
-    pub fn to_delta(&self) -> (isize, isize) {
-        match self {
-            XmasDirection::Right => (1, 0),
-            XmasDirection::BottomRight => (1, 1),
-            XmasDirection::Bottom => (0, 1),
-            XmasDirection::BottomLeft => (-1, 1),
-            XmasDirection::Left => (-1, 0),
-            XmasDirection::TopLeft => (-1, -1),
-            XmasDirection::Top => (0, -1),
-            XmasDirection::TopRight => (1, -1)
-        }
-    }
-
-    pub fn rotate_clockwise(&self) -> Self {
-        match self {
-            XmasDirection::Right => XmasDirection::BottomRight,
-            XmasDirection::BottomRight => XmasDirection::Bottom,
-            XmasDirection::Bottom => XmasDirection::BottomLeft,
-            XmasDirection::BottomLeft => XmasDirection::Left,
-            XmasDirection::Left => XmasDirection::TopLeft,
-            XmasDirection::TopLeft => XmasDirection::Top,
-            XmasDirection::Top => XmasDirection::TopRight,
-            XmasDirection::TopRight => XmasDirection::Right
-        }
-    }
-
-    pub fn rotate_counter_clockwise(&self) -> Self {
-        match self {
-            XmasDirection::Right => XmasDirection::TopRight,
-            XmasDirection::BottomRight => XmasDirection::Right,
-            XmasDirection::Bottom => XmasDirection::BottomRight,
-            XmasDirection::BottomLeft => XmasDirection::Bottom,
-            XmasDirection::Left => XmasDirection::BottomLeft,
-            XmasDirection::TopLeft => XmasDirection::Left,
-            XmasDirection::Top => XmasDirection::TopLeft,
-            XmasDirection::TopRight => XmasDirection::Top,
-        }
-    }
-
-    pub fn opposite(&self) -> Self {
-        match self {
-            XmasDirection::Right => XmasDirection::Left,
-            XmasDirection::BottomRight => XmasDirection::TopLeft,
-            XmasDirection::Bottom => XmasDirection::Top,
-            XmasDirection::BottomLeft => XmasDirection::TopRight,
-            XmasDirection::Left => XmasDirection::Right,
-            XmasDirection::TopLeft => XmasDirection::BottomRight,
-            XmasDirection::Top => XmasDirection::Bottom,
-            XmasDirection::TopRight => XmasDirection::BottomLeft,
-        }
-    }
+use crate::advent_utils::{AocError, Dir8, Direction, Grid, Point, Tile};
 
-    pub fn array() -> [Self;8] {
-        [
-            XmasDirection::Right,
-            XmasDirection::BottomRight,
-            XmasDirection::Bottom,
-            XmasDirection::BottomLeft,
-            XmasDirection::Left,
-            XmasDirection::TopLeft,
-            XmasDirection::Top,
-            XmasDirection::TopRight
-        ]
-    }
-
-    pub fn corners() -> [Self;4] {
-        [
-            XmasDirection::BottomRight,
-            XmasDirection::BottomLeft,
-            XmasDirection::TopLeft,
-            XmasDirection::TopRight
-        ]
-    }
+//the letters of XMAS in order, so a letter can be compared with its index in the word
+#[derive(Tile, Copy, Clone, Eq, PartialEq)]
+pub enum Letter {
+    #[tile('X')]
+    X,
+    #[tile('M')]
+    M,
+    #[tile('A')]
+    A,
+    #[tile('S')]
+    S,
+    #[tile(' ')]
+    Edge,
+}
 
-    pub fn cardinals() -> [Self;4] {
-        [
-            XmasDirection::Right,
-            XmasDirection::Bottom,
-            XmasDirection::Left,
-            XmasDirection::Top
-        ]
-    }
+fn get_grid_delta<T>(grid: &Grid<T>, d: Dir8, start: (usize, usize), steps: usize) -> &T {
+    grid.get_point(Point::from(start) + Point::from(d) * steps as isize)
+}
 
-    pub fn get_grid_delta<'g, T>(&self, grid: &'g Vec<Vec<T>>, start: (usize, usize), steps: usize) -> Option<&'g T> {
-        let (dx, dy) = self.to_delta();
-        let (dx, dy) = (dx * steps as isize, dy * steps as isize);
-        if start.0 as isize + dx >= 0 && start.1 as isize + dy >= 0 {
-            grid.get((start.1 as isize + dy) as usize).and_then(|l| l.get((start.0 as isize + dx) as usize))
-        } else {
-            None
-        }
-    }
+pub fn parse(input: &str) -> Result<Grid<Letter>, AocError> {
+    Grid::parse_with(input, Letter::Edge, |_, _, c| match Letter::try_from(c)? {
+        Letter::Edge => Err(AocError::parse(format!("the input should not have this char: '{c}'"))),
+        letter => Ok(letter),
+    })
 }
 
-pub fn y2024d4a(input: &str) -> Result<String, String> {
+pub fn y2024d4a(grid: &Grid<Letter>) -> Result<u32, AocError> {
     let mut count = 0;
-    let grid = input.split("\n").map(|l| {
-        l.as_ascii().unwrap().iter().map(|c| match c.to_char() {
-            'X' => 0,
-            'M' => 1,
-            'A' => 2,
-            'S' => 3,
-            c @ _ => unreachable!("the input should not have this char: {}", c)
-        }).collect::<Vec<u8>>()
-    }).collect::<Vec<Vec<u8>>>();
-    for y in 0..grid.len(){
-        for x in 0..grid[y].len() {
-            if grid[y][x] != 0 { continue }
-            for d in XmasDirection::array() {
-                for i in 1..4 {
-                    if !d.get_grid_delta(&grid, (x, y), i).map_or(false, |v| *v == i as u8) {
-                        break;
-                    }
-                    if i == 3 {
-                        count += 1;
-                    }
+    for ((x, y), letter) in grid.iter() {
+        if *letter != Letter::X { continue }
+        for d in Dir8::iter() {
+            for i in 1..4 {
+                if *get_grid_delta(grid, d, (x, y), i) as usize != i {
+                    break;
+                }
+                if i == 3 {
+                    count += 1;
                 }
             }
         }
     }
-    Ok(count.to_string())
+    Ok(count)
 }
 
-pub fn y2024d4b(input: &str) -> Result<String, String> {
+pub fn y2024d4b(grid: &Grid<Letter>) -> Result<u32, AocError> {
     let mut count = 0;
-    let grid = input.split("\n").map(|l| {
-        l.as_ascii().unwrap().iter().map(|c| match c.to_char() {
-            'X' => 3,
-            'M' => 1,
-            'A' => 0,
-            'S' => 2,
-            c @ _ => unreachable!("the input should not have this char: {}", c)
-        }).collect::<Vec<u8>>()
-    }).collect::<Vec<Vec<u8>>>();
-    for y in 0..grid.len() {
-        'main_loop: for x in 0..grid[y].len() {
-            if grid[y][x] != 0 { continue }
-            for d in XmasDirection::corners() { //find first m, clockwise -> check if opposite is s, find next m quarter clockwise/counter clockwise, check opposite s
-                if d.get_grid_delta(&grid, (x, y), 1).map_or(false, |v| *v == 1) {
-                    if d.opposite().get_grid_delta(&grid, (x, y), 1).map_or(false, |v| *v == 2) {
-                        let d = d.rotate_clockwise().rotate_clockwise();
-                        let t = d.get_grid_delta(&grid, (x, y), 1).map_or(0, |v| *v);
-                        if t == 1 || t == 2 {
-                            if d.opposite().get_grid_delta(& grid, (x, y), 1).map_or(false, |v| (*v == 2 && t == 1) || (*v == 1 && t == 2)) {
-                                count += 1;
-                                continue 'main_loop;
-                            }
-                        }
-                    }
+    'main_loop: for ((x, y), letter) in grid.iter() {
+        if *letter != Letter::A { continue }
+        for d in Dir8::DIAGONALS { //find first m, clockwise -> check if opposite is s, find next m quarter clockwise/counter clockwise, check opposite s
+            if *get_grid_delta(grid, d, (x, y), 1) == Letter::M && *get_grid_delta(grid, d.reverse(), (x, y), 1) == Letter::S {
+                let d = d.turn(2);
+                let t = *get_grid_delta(grid, d, (x, y), 1);
+                let o = *get_grid_delta(grid, d.reverse(), (x, y), 1);
+                if matches!((t, o), (Letter::M, Letter::S) | (Letter::S, Letter::M)) {
+                    count += 1;
+                    continue 'main_loop;
                 }
             }
         }
     }
-    Ok(count.to_string())
+    Ok(count)
+}
+
+pub fn y2024d4a_search(grid: &Grid<Letter>) -> Result<usize, AocError> {
+    Ok(grid.find_word(&[Letter::X, Letter::M, Letter::A, Letter::S], Dir8::iter()).len())
+}
+
+//the 4 orientations of the cross are the distinct symmetries of the pattern
+pub fn y2024d4b_search(grid: &Grid<Letter>) -> Result<usize, AocError> {
+    Ok(grid.find_pattern_symmetries(&Grid::parse_pattern("M.S\n.A.\nM.S", '.')?).len())
 }
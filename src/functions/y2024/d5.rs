@@ -1,28 +1,28 @@
+use std::cmp::Ordering;
 use std::collections::HashSet;
+use crate::advent_utils::{sections, AocError};
+use crate::generators::Rng;
 
-fn parse(input: &str) -> Result<(HashSet<(u32, u32)>, Vec<Vec<u32>>), String> {
-    use nom::Parser;
-    use nom::character::complete::u32 as int;
+pub type Precedence = HashSet<(u32, u32)>;
+
+pub fn parse(input: &str) -> Result<(Precedence, Vec<Vec<u32>>), AocError> {
+    use nom::character::complete::{u32 as int, line_ending};
     use nom::bytes::complete::tag;
-    use nom::sequence::{terminated, separated_pair};
-    use nom::multi::{many0, separated_list0, separated_list1};
+    use nom::sequence::separated_pair;
+    use nom::multi::{separated_list0, separated_list1};
+    use nom::combinator::all_consuming;
 
-    let l = many0(terminated(separated_pair(int::<&str, ()>, tag("|"), int), tag("\n"))).map(|r| {
-        let mut out = HashSet::new();
-        for (k, v) in r {
-            out.insert((k, v));
-        }
-        out
-    });
-    let p = separated_list0(tag("\n"), separated_list1(tag(","), int));
-    separated_pair(l, tag("\n"), p)(input).map_err(|e| e.to_string()).map(|(_, r)| r)
+    let [rules, updates] = sections(input)?;
+    let rules = all_consuming(separated_list1(line_ending, separated_pair(int, tag("|"), int)))(rules)?.1;
+    let updates = all_consuming(separated_list0(line_ending, separated_list1(tag(","), int)))(updates)?.1;
+    Ok((rules.into_iter().collect(), updates))
 }
 
-fn middle(sequence: &Vec<u32>) -> u32 {
+fn middle(sequence: &[u32]) -> u32 {
     sequence[sequence.len() / 2]
 }
 
-fn check_valid_order(sequence: &Vec<u32>, precedence: &HashSet<(u32, u32)>) -> bool {
+fn check_valid_order(sequence: &[u32], precedence: &Precedence) -> bool {
     for left in 0..(sequence.len() - 1) {
         for right in left + 1..sequence.len() {
             if !precedence.contains(&(sequence[left], sequence[right])) {
@@ -33,8 +33,8 @@ fn check_valid_order(sequence: &Vec<u32>, precedence: &HashSet<(u32, u32)>) -> b
     true
 }
 
-fn reorder(sequence: &Vec<u32>, precedence: &HashSet<(u32, u32)>) -> Vec<u32> {
-    let mut out = sequence.clone();
+fn reorder(sequence: &[u32], precedence: &Precedence) -> Vec<u32> {
+    let mut out = sequence.to_vec();
     let mut left = 0;
     'main: loop {
         if left >= out.len() {
@@ -42,9 +42,7 @@ fn reorder(sequence: &Vec<u32>, precedence: &HashSet<(u32, u32)>) -> Vec<u32> {
         }
         for right in left + 1..out.len() {
             if !precedence.contains(&(out[left], out[right])) {
-                let t = out[left];
-                out[left] = out[right];
-                out[right] = t;
+                out.swap(left, right);
                 continue 'main;
             }
         }
@@ -53,24 +51,71 @@ fn reorder(sequence: &Vec<u32>, precedence: &HashSet<(u32, u32)>) -> Vec<u32> {
     out
 }
 
-pub fn y2024d5a(input: &str) -> Result<String, String> {
-    let (precedence, pages) = parse(input)?;
+pub fn y2024d5a((precedence, pages): &(Precedence, Vec<Vec<u32>>)) -> Result<u32, AocError> {
     let mut acc = 0;
-    for sequence in &pages {
-        if check_valid_order(sequence, &precedence) {
+    for sequence in pages {
+        if check_valid_order(sequence, precedence) {
             acc += middle(sequence);
         }
     }
-    Ok(acc.to_string())
+    Ok(acc)
 }
 
-pub fn y2024d5b(input: &str) -> Result<String, String> {
-    let (precedence, pages) = parse(input)?;
+pub fn y2024d5b((precedence, pages): &(Precedence, Vec<Vec<u32>>)) -> Result<u32, AocError> {
     let mut acc = 0;
-    for sequence in &pages {
-        if !check_valid_order(sequence, &precedence) {
-            acc += middle(&reorder(sequence, &precedence));
+    for sequence in pages {
+        if !check_valid_order(sequence, precedence) {
+            acc += middle(&reorder(sequence, precedence));
         }
     }
-    Ok(acc.to_string())
+    Ok(acc)
+}
+
+//sort using the precedence rules as a comparator instead of swapping until the order is valid
+pub fn y2024d5b_sort((precedence, pages): &(Precedence, Vec<Vec<u32>>)) -> Result<u32, AocError> {
+    let mut acc = 0;
+    for sequence in pages {
+        if !check_valid_order(sequence, precedence) {
+            let mut sorted = sequence.clone();
+            sorted.sort_by(|l, r| if precedence.contains(&(*l, *r)) { Ordering::Less } else if precedence.contains(&(*r, *l)) { Ordering::Greater } else { Ordering::Equal });
+            acc += middle(&sorted);
+        }
+    }
+    Ok(acc)
+}
+
+//a random total order of size pages (at least 5) with a rule for each pair, then size updates of an odd number of pages in any order
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5);
+    let mut order: Vec<usize> = (10..10 + size).collect();
+    rng.shuffle(&mut order);
+    let mut rules = Vec::new();
+    for left in 0..size {
+        for right in left + 1..size {
+            rules.push(format!("{}|{}", order[left], order[right]));
+        }
+    }
+    rng.shuffle(&mut rules);
+    let updates = (0..size).map(|_| {
+        let mut pages = order.clone();
+        rng.shuffle(&mut pages);
+        let len = 5 + 2 * rng.index((size.min(23) - 5) / 2 + 1);
+        pages[..len].iter().map(|p| p.to_string()).collect::<Vec<String>>().join(",")
+    }).collect::<Vec<String>>();
+    format!("{}\n\n{}", rules.join("\n"), updates.join("\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error_at(input: &str) -> Option<(usize, usize)> {
+        parse(input).unwrap_err().locate(input).location.map(|l| (l.line, l.column))
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(error_at("47|53\n1x3\n\n75,47,61\n"), Some((1, 6)));
+        assert_eq!(error_at("47|53\n\n75,47,61\n3,2,1 x\n1;2\n"), Some((4, 6)));
+    }
 }
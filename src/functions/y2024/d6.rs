@@ -1,125 +1,40 @@
 use std::collections::HashSet;
-use std::fmt::{Debug, Formatter, Write};
+use crate::advent_utils::{stdin_commands, Animation, AocError, Cell, Dir4, Direction, Grid, Point, Tile};
+use crate::generators::Rng;
+use crate::profile::Ctx;
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Tiles {
+#[derive(Tile, Copy, Clone, Eq, PartialEq)]
+pub enum Tiles {
+    #[tile('.')]
     Empty,
+    #[tile('#')]
     Crate,
+    #[tile('X')]
     Walked,
+    #[tile(' ')]
     Edge
 }
 
 impl Tiles {
     fn walkable(&self) -> bool {
-        match self {
-            Tiles::Crate => false,
-            _ => true
-        }
+        !matches!(self, Tiles::Crate)
     }
 }
 
-struct Map {
-    width: usize,
-    height: usize,
-    tiles: Vec<Tiles>,
-}
-
-impl Map {
-    fn new(width: usize, height: usize) -> Self {
-        Self {
-            width,
-            height,
-            tiles: vec![Tiles::Empty; width * height],
-        }
-    }
-
-    fn set(&mut self, x: isize, y: isize, tile: Tiles) -> Tiles {
-        if x < 0 || x >= self.width as isize || y < 0 || y >= self.height as isize {
-            return Tiles::Edge;
-        }
-        let prev = self.tiles[x as usize + y as usize * self.width];
-        self.tiles[x as usize + y as usize * self.width] = tile;
-        prev
-    }
-
-    fn get(&self, x: isize, y: isize) -> Tiles {
-        if x < 0 || y < 0 || x >= self.width as isize || y >= self.height as isize {
-            return Tiles::Edge;
-        }
-        self.tiles[x as usize + y as usize * self.width]
-    }
-
-    fn print(&self, guard: &Guard) {
-        let gx = if guard.x < 0 { 0 } else if guard.x >= self.width as isize { self.width - 1 } else { guard.x as usize };
-        let gy = if guard.y < 0 { 0 } else if guard.y >= self.height as isize { self.height - 1 } else { guard.y as usize };
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if gx == x && gy == y  {
-                    print!("{}", match guard.dir {
-                        Direction::Up => '^',
-                        Direction::Down => 'v',
-                        Direction::Left => '<',
-                        Direction::Right => '>',
-                    });
-                } else {
-                    print!("{}", match self.tiles[x + self.width * y] {
-                        Tiles::Empty => '.',
-                        Tiles::Crate => '#',
-                        Tiles::Walked => 'X',
-                        Tiles::Edge => ' ',
-                    });
-                }
-            }
-            println!();
-        }
-    }
-}
-
-#[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
-enum Direction {
-    #[default]
-    Up,
-    Down,
-    Left,
-    Right
-}
-
-impl Debug for Direction {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_char(match self {
-            Direction::Up => '^',
-            Direction::Down => 'v',
-            Direction::Left => '<',
-            Direction::Right => '>',
-        })
-    }
-}
-
-impl Direction {
-    fn delta(&self) -> (isize, isize) {
-        match self {
-            Self::Up => (0, -1),
-            Self::Down => (0, 1),
-            Self::Left => (-1, 0),
-            Self::Right => (1, 0),
-        }
-    }
+pub type Map = Grid<Tiles>;
 
-    fn rotate(&self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        }
+fn cell(tile: &Tiles) -> Cell {
+    match tile {
+        Tiles::Crate => Cell::new('#', Some([200, 120, 40])),
+        Tiles::Walked => Cell::new('X', Some([80, 160, 255])),
+        t => Cell::new((*t).into(), None),
     }
 }
 
 #[derive(Default, Copy, Clone, Hash, Eq, PartialEq, Debug)]
-struct Guard {
-    x: isize,
-    y: isize,
-    dir: Direction,
+pub struct Guard {
+    pos: Point,
+    dir: Dir4,
 }
 
 #[derive(Default)]
@@ -129,7 +44,9 @@ struct PositionSet {
 }
 
 impl PositionSet {
-    fn add(&mut self, position: Guard) {
+    //called millions of times by y2024d6b, the span doubles its time when profiling (the benchmarks use a disabled ctx)
+    fn add(&mut self, position: Guard, ctx: &Ctx) {
+        let _s = ctx.span("set insertions");
         self.vec.push(position);
         self.set.insert(position);
     }
@@ -142,11 +59,11 @@ impl PositionSet {
         &self.vec
     }
 
-    fn clone_until(&self, position: Guard) -> Self {
+    fn clone_until(&self, position: Guard, ctx: &Ctx) -> Self {
         let mut clone = Self::default();
         for guard in &self.vec {
             if guard == &position { break }
-            clone.add(*guard);
+            clone.add(*guard, ctx);
         }
         clone
     }
@@ -154,111 +71,66 @@ impl PositionSet {
 
 impl Guard {
     fn step(&self) -> Self {
-        let (dx, dy) = self.dir.delta();
         Self {
-            x: self.x + dx,
-            y: self.y + dy,
+            pos: self.pos.step(self.dir),
             dir: self.dir,
         }
     }
 
     fn rotate(&self) -> Self {
         Self {
-            x: self.x,
-            y: self.y,
-            dir: self.dir.rotate(),
+            pos: self.pos,
+            dir: self.dir.turn_right(),
         }
     }
 
     fn get_tile_in_front(&self, map: &Map) -> Tiles {
-        let (dx, dy) = self.dir.delta();
-        map.get(self.x + dx, self.y + dy)
+        *map.get_point(self.pos.step(self.dir))
     }
 
     fn get_tile_under(&self, map: &Map) -> Tiles {
-        map.get(self.x, self.y)
+        *map.get_point(self.pos)
     }
 
     fn paint(&self, map: &mut Map, tile: Tiles) -> bool {
-        map.set(self.x, self.y, tile) != tile
+        map.set_point(self.pos, tile) != tile
     }
 }
 
-fn parse(input: &str) -> Result<(Map, Guard), String> {
-    let mut width = 0;
-    let mut height = 0;
-    let mut x = 0;
-    for c in input.chars() {
-        match c {
-            '^' | '>' | '<' | 'v' | '.' | '#' => {
-                if x == 0 {
-                    height += 1;
-                }
-                x += 1;
-                if height == 1 {
-                    width += 1;
-                } else if x > width {
-                    return Err(format!("Line {height} too long {x} expected {width}"));
-                }
-            }
-            '\n' => { x = 0; }
-            '\r' => {}
-            e @ _ => {
-                return Err(format!("Invalid character '{e}'"));
-            }
-        }
-    }
-    let mut map = Map::new(width, height);
+pub fn parse(input: &str) -> Result<(Map, Guard), AocError> {
     let mut guard: Option<Guard> = None;
-    let mut x = 0;
-    let mut y = 0;
-    for c in input.chars() {
-        match c {
-            '^' | '>' | '<' | 'v' | '.' | '#' => {
-                let tile = if c == '#' { Tiles::Crate } else { Tiles::Empty };
-                if c != '#' && c != '.' {
-                    if guard.is_some() {
-                        return Err("Duplicate guard".to_string());
-                    }
-                    guard = Some(Guard {
-                        x,
-                        y,
-                        dir: match c {
-                            '^' => Direction::Up,
-                            '>' => Direction::Right,
-                            '<' => Direction::Left,
-                            'v' => Direction::Down,
-                            _ => unreachable!()
-                        }
-                    });
-                }
-                map.set(x, y, tile);
-                x += 1;
-            }
-            '\n' => {
-                y += 1;
-                x = 0;
-            }
-            _ => {}
+    let map = Grid::parse_with(input, Tiles::Edge, |x, y, c| {
+        let dir = match c {
+            '^' | '>' | 'v' | '<' => Dir4::try_from(c)?,
+            c => return match Tiles::try_from(c)? {
+                tile @ (Tiles::Empty | Tiles::Crate) => Ok(tile),
+                _ => Err(AocError::parse(format!("Invalid character '{c}'"))),
+            },
+        };
+        if guard.is_some() {
+            return Err(AocError::parse("Duplicate guard"));
         }
-    }
+        guard = Some(Guard { pos: Point::from((x, y)), dir });
+        Ok(Tiles::Empty)
+    })?;
     if let Some(guard) = guard {
         Ok((map, guard))
     } else {
-        Err("no guard found".to_string())
+        Err(AocError::parse("no guard found"))
     }
 }
 
-fn guard_patrol(map: &mut Map, mut guard: Guard, mut positions: PositionSet) -> (PositionSet, Option<usize>) {
+//ctx times the insertions in the set of positions, pass a disabled one when not profiling
+fn guard_patrol(map: &mut Map, mut guard: Guard, mut positions: PositionSet, ctx: &Ctx) -> (PositionSet, Option<usize>) {
     let mut acc = 0;
-    while guard.get_tile_under(&map) != Tiles::Edge {
+    while guard.get_tile_under(map) != Tiles::Edge {
         if guard.paint(map, Tiles::Walked) {
             acc += 1;
         } else if positions.contains(guard) {
             return (positions, None);
         }
-        positions.add(guard);
-        if guard.get_tile_in_front(&map).walkable() {
+        positions.add(guard, ctx);
+        if guard.get_tile_in_front(map).walkable() {
             guard = guard.step();
         } else {
             guard = guard.rotate();
@@ -267,30 +139,87 @@ fn guard_patrol(map: &mut Map, mut guard: Guard, mut positions: PositionSet) ->
     (positions, Some(acc))
 }
 
-pub fn y2024d6a(input: &str) -> Result<String, String> {
-    let (mut map, guard) = parse(input)?;
-    guard_patrol(&mut map, guard, PositionSet::default()).1.ok_or_else(|| "Looping".to_string()).map(|r| r.to_string())
+pub fn y2024d6a((map, guard): &(Map, Guard)) -> Result<usize, AocError> {
+    guard_patrol(&mut map.clone(), *guard, PositionSet::default(), &Ctx::disabled()).1.ok_or_else(|| AocError::no_solution("Looping"))
 }
 
 //works in under 2 seconds in release mode, almost 15 seconds in debug mode
-pub fn y2024d6b(input: &str) -> Result<String, String> {
-    let (mut map, guard) = parse(input)?;
-    let (positions, _) = guard_patrol(&mut map, guard, PositionSet::default());
+pub fn y2024d6b((map, guard): &(Map, Guard), ctx: &Ctx) -> Result<u32, AocError> {
+    let mut map = map.clone();
+    let (positions, _) = {
+        let _s = ctx.span("first patrol");
+        guard_patrol(&mut map, *guard, PositionSet::default(), ctx)
+    };
     let mut acc = 0;
     let mut tries = HashSet::new();
     for position in positions.ordered() {
         let t = position.step();
-        if !tries.contains(&(t.x, t.y)) {
+        if !tries.contains(&t.pos) {
             let front = t.get_tile_under(&map);
             if front.walkable() && front != Tiles::Edge {
                 t.paint(&mut map, Tiles::Crate);
-                tries.insert((t.x, t.y));
-                if guard_patrol(&mut map, *position, positions.clone_until(*position)).1.is_none() {
+                tries.insert(t.pos);
+                let previous = {
+                    let _s = ctx.span("clone_until");
+                    positions.clone_until(*position, ctx)
+                };
+                let looping = {
+                    let _s = ctx.span("guard_patrol");
+                    guard_patrol(&mut map, *position, previous, ctx).1.is_none()
+                };
+                if looping {
                     acc += 1;
                 }
                 t.paint(&mut map, Tiles::Walked);
             }
         }
     }
-    Ok(acc.to_string())
+    Ok(acc)
+}
+
+//naive version: restart the patrol from the beginning for each obstacle put on the path of the guard
+pub fn y2024d6b_naive((map, guard): &(Map, Guard)) -> Result<u32, AocError> {
+    let mut walked = map.clone();
+    let ctx = Ctx::disabled();
+    guard_patrol(&mut walked, *guard, PositionSet::default(), &ctx);
+    let mut acc = 0;
+    for (p, tile) in walked.iter() {
+        let p = Point::from(p);
+        if p != guard.pos && *tile == Tiles::Walked {
+            let mut blocked = map.clone();
+            blocked.set_point(p, Tiles::Crate);
+            if guard_patrol(&mut blocked, *guard, PositionSet::default(), &ctx).1.is_none() {
+                acc += 1;
+            }
+        }
+    }
+    Ok(acc)
+}
+
+//animate the patrol in the terminal: enter shows the next step, p plays or pauses, q stops
+pub fn watch(input: &str, fps: f64) -> Result<(), AocError> {
+    let (mut map, mut guard) = parse(input)?;
+    let mut animation = Animation::new(std::io::stdout(), fps).with_commands(stdin_commands());
+    while guard.get_tile_under(&map) != Tiles::Edge && animation.is_running() {
+        guard.paint(&mut map, Tiles::Walked);
+        animation.render(map.width(), map.height(), |x, y| {
+            if Point::from((x, y)) == guard.pos { Cell::new(guard.dir.arrow(), Some([255, 220, 0])) } else { cell(map.get(x, y)) }
+        }).map_err(|e| AocError::from(e.to_string()))?;
+        guard = if guard.get_tile_in_front(&map).walkable() { guard.step() } else { guard.rotate() };
+    }
+    Ok(())
+}
+
+//size x size map with a few crates, maps where the guard never leaves are discarded
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    loop {
+        let mut tiles: Vec<Vec<char>> = (0..size).map(|_| (0..size).map(|_| if rng.chance(0.02) { '#' } else { '.' }).collect()).collect();
+        let (x, y) = (rng.index(size), rng.index(size));
+        tiles[y][x] = '^';
+        let input = tiles.iter().map(|l| l.iter().collect::<String>()).collect::<Vec<String>>().join("\n");
+        if let Ok((mut map, guard)) = parse(&input) && guard_patrol(&mut map, guard, PositionSet::default(), &Ctx::disabled()).1.is_some() {
+            return input;
+        }
+    }
 }
@@ -1,35 +1,38 @@
-fn parser(input: &str) -> Result<Vec<(u64, Vec<u64>)>, String> {
-    use nom::Parser;
-    use nom::character::complete::u64 as int;
+use crate::advent_utils::AocError;
+use crate::generators::Rng;
+
+pub fn parse(input: &str) -> Result<Vec<(u64, Vec<u64>)>, AocError> {
+    use nom::character::complete::{u64 as int, line_ending};
     use nom::bytes::complete::tag;
-    use nom::sequence::separated_pair;
+    use nom::sequence::{separated_pair, terminated};
     use nom::multi::separated_list1;
+    use nom::combinator::{all_consuming, opt};
 
-    separated_list1(
-        tag("\n"),
+    all_consuming(terminated(separated_list1(
+        line_ending,
         separated_pair(
             int,
             tag(": "),
             separated_list1(
                 tag(" "),
-                int::<&str, ()>)
+                int)
         )
-    )(input).map_err(|e| e.to_string()).map(|(_, v)| v)
+    ), opt(line_ending)))(input).map_err(AocError::from).map(|(_, v)| v)
 }
 
 #[derive(Copy, Clone, Debug)]
 enum Operators {
-    ADD,
-    MUL,
-    CAT
+    Add,
+    Mul,
+    Cat
 }
 
 impl Operators {
     fn apply(&self, left: u64, right: u64) -> u64 {
         match self {
-            Operators::ADD => left + right,
-            Operators::MUL => left * right,
-            Operators::CAT => if left > 0 && right > 0 {
+            Operators::Add => left + right,
+            Operators::Mul => left * right,
+            Operators::Cat => if left > 0 && right > 0 {
                 (left.to_string() + &right.to_string()).parse::<u64>().unwrap_or(u64::MAX)
             } else {
                 left + right
@@ -38,11 +41,11 @@ impl Operators {
     }
 
     fn a_array() -> [Operators; 2] {
-        [Operators::ADD, Operators::MUL]
+        [Operators::Add, Operators::Mul]
     }
 
     fn b_array() -> [Operators; 3] {
-        [Operators::ADD, Operators::MUL, Operators::CAT]
+        [Operators::Add, Operators::Mul, Operators::Cat]
     }
 }
 
@@ -67,27 +70,50 @@ fn rec(res: u64, mut acc: u64, mut pos: usize, op: Operators, input: &Vec<u64>,
 }
 
 fn calc(res: u64, input: &Vec<u64>, operators: &[Operators]) -> bool {
-    rec(res, 0, 0, Operators::ADD, input, operators)
+    rec(res, 0, 0, Operators::Add, input, operators)
 }
 
-pub fn y2024d7a(input: &str) -> Result<String, String> {
-    let v = parser(input)?;
+pub fn y2024d7a(equations: &Vec<(u64, Vec<u64>)>) -> Result<u64, AocError> {
     let mut acc = 0;
-    for (e, v) in v {
-        if calc(e, &v, &Operators::a_array()) {
+    for (e, v) in equations {
+        if calc(*e, v, &Operators::a_array()) {
             acc += e;
         }
     }
-    Ok(acc.to_string())
+    Ok(acc)
 }
 
-pub fn y2024d7b(input: &str) -> Result<String, String> {
-    let v = parser(input)?;
+pub fn y2024d7b(equations: &Vec<(u64, Vec<u64>)>) -> Result<u64, AocError> {
     let mut acc = 0;
-    for (e, v) in v {
-        if calc(e, &v, &Operators::b_array()) {
+    for (e, v) in equations {
+        if calc(*e, v, &Operators::b_array()) {
             acc += e;
         }
     }
-    Ok(acc.to_string())
+    Ok(acc)
+}
+
+//size equations of 2 to 6 operands, most of them calibrated with random operators and a few with a random result
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| {
+        let operands: Vec<u64> = (0..rng.range(2..7)).map(|_| rng.range(1..100)).collect();
+        let result = if rng.chance(0.2) {
+            rng.range(1..1_000_000)
+        } else {
+            operands[1..].iter().fold(operands[0], |acc, v| rng.pick(&Operators::b_array()).apply(acc, *v))
+        };
+        format!("{result}: {}", operands.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(" "))
+    }).collect::<Vec<String>>().join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("190: 10 19\r\n3267: 81 40 27\r\n").map(|e| e.len()), Ok(2));
+        let input = "190: 10 19\n3267: 81 x 27\n";
+        assert_eq!(parse(input).unwrap_err().locate(input).location.map(|l| (l.line, l.column)), Some((2, 9)));
+    }
 }
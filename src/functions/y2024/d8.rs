@@ -1,5 +1,3 @@
-use crate::advent_utils;
-
 // fn parse(input: &str) -> Result<(), String> {
 //     if let Some((width, height)) = advent_utils::is_text_square(input) {
 //
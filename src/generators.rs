@@ -0,0 +1,79 @@
+use std::ops::Range;
+use crate::runner::find_day;
+
+///small deterministic random generator (splitmix64), the same seed always gives the same inputs
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    ///uniform value in the range (the range must not be empty)
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    pub fn pick<'v, T>(&mut self, values: &'v [T]) -> &'v T {
+        &values[self.index(values.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+///generate an input of the given size for a day, using its generate function (fn generate(&mut Rng, usize) -> String) if it has one
+///what the size means depends on the day (number of lines, width of a map, ...)
+pub fn generate(year: u32, day: u32, size: usize, seed: u64) -> Option<String> {
+    find_day(year, day)?.generate.map(|generate| generate(&mut Rng::new(seed), size))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        for _ in 0..100 {
+            let v = a.range(10..20);
+            assert_eq!(v, b.range(10..20));
+            assert!((10..20).contains(&v));
+        }
+        let mut values: Vec<usize> = (0..10).collect();
+        a.shuffle(&mut values);
+        values.sort();
+        assert_eq!(values, (0..10).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_generated_inputs_are_valid() {
+        for day in 1..=25 {
+            if let Some(input) = generate(2024, day, 50, 7) {
+                assert_eq!(generate(2024, day, 50, 7).as_ref(), Some(&input), "generated inputs of day {day} should be reproducible");
+                find_day(2024, day).unwrap().run(input.as_str(), None).unwrap_or_else(|e| panic!("generated input of day {day} is invalid: {e}"));
+            }
+        }
+    }
+}
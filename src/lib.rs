@@ -0,0 +1,15 @@
+#![allow(dead_code)]
+extern crate nom;
+extern crate self as advent_of_code; //lets the derives of proc_macros name the crate the same way inside and outside of it
+
+use proc_macros::advent_of_code_setup;
+
+pub mod p2024;
+pub mod functions;
+pub mod advent_utils;
+pub mod runner;
+pub mod bench;
+pub mod profile;
+pub mod generators;
+
+advent_of_code_setup!(years = 2024..=2024);
@@ -1,40 +1,113 @@
-#![feature(ascii_char)]
-#![feature(let_chains)]
-
-#![allow(dead_code)]
-extern crate nom;
-
 use std::env::args;
 use std::fs;
 use std::fs::{OpenOptions};
 use std::io::{Write};
-use proc_macros::advent_of_code_setup;
+use advent_of_code::{runner, DAYS};
+use advent_of_code::bench::{bench_day, Baseline, BenchConfig};
+use advent_of_code::generators::generate;
 use utils::*;
 
-mod p2024;
-mod functions;
-mod advent_utils;
+fn run_day(year: u32, day: u32, variant: Option<&str>) {
+    if let Ok(input) = fs::read_to_string(format!("inputs/{year}/{day}.txt")) && let Some(d) = runner::find_day(year, day) {
+        let report = match d.run(input.as_str(), variant) {
+            Ok(report) => report,
+            Err(e) => {
+                println!("{year}/12/{day} -> error: {e}");
+                return;
+            }
+        };
+        let (a, b) = (&report.a.answer, &report.b.answer);
+        println!("{year}/12/{day} -> a: {}, b: {}", a, b);
+        if let Some(parse) = report.parse {
+            println!("    parse: {parse:?}");
+        }
+        println!("    a: {}", report.a.timings());
+        println!("    b: {}", report.b.timings());
+        if report.profile.is_detailed() {
+            println!("    profile:");
+            for line in report.profile.to_string().lines() {
+                println!("    {line}");
+            }
+        }
+        let mut fa = OpenOptions::new().create(true).write(true).open(format!("outputs/{year}/{day}a.txt")).unwrap_or_else(|_| panic!("Cannot create output for day {day} of year {year}"));
+        let mut fb = OpenOptions::new().create(true).write(true).open(format!("outputs/{year}/{day}b.txt")).unwrap_or_else(|_| panic!("Cannot create output for day {day} of year {year}"));
+        fa.write_fmt(format_args!("{a}")).unwrap_or_else(|_| panic!("could not write to outputs/{year}/{day}a.txt"));
+        fb.write_fmt(format_args!("{b}")).unwrap_or_else(|_| panic!("could not write to outputs/{year}/{day}b.txt"));
+    }
+}
 
-advent_of_code_setup!();
+///options of the bench subcommand
+///regressions above threshold (relative change of the median) are flagged, results without a baseline are added to it and existing ones are only replaced if save is true
+///if sizes is not empty, the days are measured on generated inputs of each size instead of the real input (results are named ..@{size}#{seed})
+struct BenchOptions {
+    variant: Option<String>,
+    baseline: String,
+    threshold: f64,
+    save: bool,
+    sizes: Vec<usize>,
+    seed: u64,
+}
 
-fn run_day(year: u32, day: u32) {
-    if let Ok(input) = fs::read_to_string(format!("inputs/{year}/{day}.txt")) {
-        let (a, b) = day_caller(year, day, input.as_str()).unwrap();
-        println!("{year}/12/{day} -> a: {}, b: {}", a, b);
-        let mut fa = OpenOptions::new().create(true).write(true).open(format!("outputs/{year}/{day}a.txt")).expect(format!("Cannot create output for day {day} of year {year}").as_str());
-        let mut fb = OpenOptions::new().create(true).write(true).open(format!("outputs/{year}/{day}b.txt")).expect(format!("Cannot create output for day {day} of year {year}").as_str());
-        fa.write_fmt(format_args!("{a}")).expect(format!("could not write to outputs/{year}/{day}a.txt").as_str());
-        fb.write_fmt(format_args!("{b}")).expect(format!("could not write to outputs/{year}/{day}b.txt").as_str());
+impl BenchOptions {
+    ///remove the options from the arguments, leaving [year [day [variant]]]
+    fn parse(args: &mut Vec<String>) -> Self {
+        let mut option = |name: &str, value: bool| args.iter().position(|a| a == name).map(|i| {
+            let v = if value { args.get(i + 1).cloned().unwrap_or_default() } else { String::new() };
+            args.drain(i..(i + 1 + value as usize).min(args.len()));
+            v
+        });
+        let baseline = option("--baseline", true).unwrap_or("outputs/bench.txt".to_string());
+        let threshold = option("--threshold", true).and_then(|t| t.parse::<f64>().ok()).unwrap_or(10.) / 100.;
+        let save = option("--save", false).is_some();
+        let sizes = option("--size", true).map(|s| s.split(',').filter_map(|n| n.trim().parse().ok()).collect()).unwrap_or_default();
+        let seed = option("--seed", true).and_then(|s| s.parse().ok()).unwrap_or(0);
+        Self { variant: args.get(3).cloned(), baseline, threshold, save, sizes, seed }
     }
 }
 
-fn parse_args(args: Vec<String>) -> Option<(u32, u32, u32)> {
+///benchmark the selected days and compare them to the baseline
+fn bench(year: u32, days: &[u32], options: &BenchOptions) {
+    let mut baseline = Baseline::load(&options.baseline);
+    let mut updated = false;
+    for &day in days {
+        let inputs: Vec<(String, String)> = if options.sizes.is_empty() {
+            //days without an input yet (empty file) are skipped, like the days without a generator
+            fs::read_to_string(format!("inputs/{year}/{day}.txt")).into_iter().filter(|input| !input.trim().is_empty()).map(|input| (input, String::new())).collect()
+        } else {
+            options.sizes.iter().filter_map(|&size| generate(year, day, size, options.seed).map(|input| (input, format!("@{size}#{}", options.seed)))).collect()
+        };
+        for (input, suffix) in inputs {
+            let Some(d) = runner::find_day(year, day) else { continue };
+            match bench_day(d, input.as_str(), options.variant.as_deref(), &BenchConfig::default()) {
+                Ok(results) => for (name, stats) in results {
+                    let name = name + &suffix;
+                    match baseline.change(name.as_str(), &stats) {
+                        Some(change) => println!("{name}: {stats} {:+.1}% vs baseline{}", change * 100., if change > options.threshold { " REGRESSION" } else { "" }),
+                        None => println!("{name}: {stats}"),
+                    }
+                    if options.save || !baseline.results.contains_key(&name) {
+                        baseline.results.insert(name, stats);
+                        updated = true;
+                    }
+                }
+                Err(e) => println!("{year}/12/{day} -> error: {e}"),
+            }
+        }
+    }
+    if updated {
+        baseline.save(&options.baseline).unwrap_or_else(|_| panic!("could not write to {}", options.baseline));
+    }
+}
+
+///usage: advent_of_code [year [day [variant]]], runs every variant of each part unless a variant is given
+///or: advent_of_code bench [year [day [variant]]] [--baseline path] [--threshold percent] [--save] [--size n,n,..] [--seed n], baseline defaults to outputs/bench.txt and threshold to 10%
+///or: advent_of_code generate year day size [seed], prints a synthetic input of the day
+///or: advent_of_code watch year day [fps], animates the input of the day in the terminal (fps defaults to 10)
+fn parse_args(args: &[String]) -> Option<(u32, u32, u32)> {
     if args.len() >= 2 {
         let year: u32 = args[1].parse().ok()?;
-        if args.len() >= 3 {
-            if let Ok(day) = args[2].parse() {
-                return Some((year, 12, day))
-            }
+        if args.len() >= 3 && let Ok(day) = args[2].parse() {
+            return Some((year, 12, day))
         }
         Some((year, 12, 31))
     } else {
@@ -43,12 +116,50 @@ fn parse_args(args: Vec<String>) -> Option<(u32, u32, u32)> {
 }
 
 fn main() {
-    if let Some((year, month, day)) = parse_args(args().collect()) {
+    let mut args: Vec<String> = args().collect();
+    if args.get(1).is_some_and(|a| a == "bench") {
+        args.remove(1);
+        let options = BenchOptions::parse(&mut args);
+        if let Some((year, month, day)) = parse_args(&args) {
+            let days: Vec<u32> = if month == 12 && day <= 25 { vec![day] } else { DAYS.iter().filter(|d| d.year == year).map(|d| d.day).collect() };
+            bench(year, &days, &options);
+        }
+        return;
+    }
+    if args.get(1).is_some_and(|a| a == "generate") {
+        let number = |i: usize| args.get(i).and_then(|n| n.parse::<u64>().ok());
+        match (number(2), number(3), number(4)) {
+            (Some(year), Some(day), Some(size)) => match generate(year as u32, day as u32, size as usize, number(5).unwrap_or(0)) {
+                Some(input) => println!("{input}"),
+                None => println!("{year}/12/{day} has no generator"),
+            },
+            _ => println!("usage: generate year day size [seed]"),
+        }
+        return;
+    }
+    if args.get(1).is_some_and(|a| a == "watch") {
+        let number = |i: usize| args.get(i).and_then(|n| n.parse::<u32>().ok());
+        let (Some(year), Some(day)) = (number(2), number(3)) else {
+            println!("usage: watch year day [fps]");
+            return;
+        };
+        let fps = args.get(4).and_then(|f| f.parse::<f64>().ok()).unwrap_or(10.);
+        match (runner::find_day(year, day).and_then(|d| d.watch), fs::read_to_string(format!("inputs/{year}/{day}.txt"))) {
+            (None, _) => println!("{year}/12/{day} has no animation"),
+            (_, Err(_)) => println!("could not read inputs/{year}/{day}.txt"),
+            (Some(watch), Ok(input)) => if let Err(e) = watch(input.as_str(), fps) {
+                println!("{year}/12/{day} -> error: {e}");
+            },
+        }
+        return;
+    }
+    let variant = args.get(3).map(|v| v.as_str());
+    if let Some((year, month, day)) = parse_args(&args) {
         if month == 12 && day <= 25 {
-            run_day(year, day);
+            run_day(year, day, variant);
         } else {
-            for i in 1..=25 {
-                run_day(year, i);
+            for d in DAYS.iter().filter(|d| d.year == year) {
+                run_day(year, d.day, variant);
             }
         }
     }
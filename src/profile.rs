@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+///aggregated time spent in a span, spans with the same name and the same parent are merged (count is the number of times it was entered)
+#[derive(Clone, Debug, Default)]
+pub struct ProfileNode {
+    pub name: String,
+    pub total: Duration,
+    pub count: usize,
+    pub children: Vec<usize>,
+    parent: usize,
+}
+
+///tree of the spans recorded by a Ctx, node 0 is the root (the whole day)
+#[derive(Clone, Debug)]
+pub struct Profile {
+    pub nodes: Vec<ProfileNode>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            nodes: vec![ProfileNode::default()],
+        }
+    }
+}
+
+impl Profile {
+    ///true if the spans did more than wrapping the parts (a solution opened spans of its own)
+    pub fn is_detailed(&self) -> bool {
+        self.nodes[0].children.iter().any(|c| !self.nodes[*c].children.is_empty())
+    }
+
+    fn fmt_node(&self, f: &mut Formatter<'_>, node: usize, depth: usize) -> std::fmt::Result {
+        let n = &self.nodes[node];
+        if node != 0 {
+            write!(f, "{}{} {:?}", "    ".repeat(depth), n.name, n.total)?;
+            if n.count > 1 {
+                write!(f, " ({} calls)", n.count)?;
+            }
+            writeln!(f)?;
+        }
+        for c in &n.children {
+            self.fmt_node(f, *c, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_node(f, 0, 0)
+    }
+}
+
+///context given to the parts that take a second argument, used to time the phases of a solution: let _s = ctx.span("patrol");
+///a disabled context (used by the benchmarks) does not record anything
+pub struct Ctx {
+    enabled: bool,
+    profile: RefCell<Profile>,
+    current: RefCell<usize>,
+}
+
+impl Ctx {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            profile: RefCell::new(Profile::default()),
+            current: RefCell::new(0),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new()
+        }
+    }
+
+    ///open a span nested in the current one, it is closed (and its duration recorded) when dropped
+    pub fn span(&self, name: &str) -> Span<'_> {
+        if !self.enabled {
+            return Span { ctx: self, node: None, start: None };
+        }
+        let mut profile = self.profile.borrow_mut();
+        let parent = *self.current.borrow();
+        let node = if let Some(node) = profile.nodes[parent].children.iter().find(|c| profile.nodes[**c].name == name) {
+            *node
+        } else {
+            let node = profile.nodes.len();
+            profile.nodes.push(ProfileNode { name: name.to_string(), parent, ..ProfileNode::default() });
+            profile.nodes[parent].children.push(node);
+            node
+        };
+        *self.current.borrow_mut() = node;
+        Span { ctx: self, node: Some(node), start: Some(Instant::now()) }
+    }
+
+    pub fn profile(&self) -> Profile {
+        self.profile.borrow().clone()
+    }
+}
+
+impl Default for Ctx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Span<'c> {
+    ctx: &'c Ctx,
+    node: Option<usize>,
+    start: Option<Instant>,
+}
+
+impl Drop for Span<'_> {
+    fn drop(&mut self) {
+        if let (Some(node), Some(start)) = (self.node, self.start) {
+            let elapsed = start.elapsed();
+            let mut profile = self.ctx.profile.borrow_mut();
+            profile.nodes[node].total += elapsed;
+            profile.nodes[node].count += 1;
+            *self.ctx.current.borrow_mut() = profile.nodes[node].parent;
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_nested_spans() {
+        let ctx = Ctx::new();
+        {
+            let _a = ctx.span("a");
+            for _ in 0..3 {
+                let _b = ctx.span("b");
+            }
+            let _c = ctx.span("c");
+        }
+        let profile = ctx.profile();
+        let names = |n: usize| profile.nodes[n].children.iter().map(|c| (profile.nodes[*c].name.as_str(), profile.nodes[*c].count)).collect::<Vec<(&str, usize)>>();
+        assert_eq!(names(0), vec![("a", 1)]);
+        assert_eq!(names(profile.nodes[0].children[0]), vec![("b", 3), ("c", 1)]);
+        assert!(profile.is_detailed());
+        let disabled = Ctx::disabled();
+        drop(disabled.span("a"));
+        assert_eq!(disabled.profile().nodes.len(), 1);
+    }
+}
@@ -0,0 +1,174 @@
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use crate::advent_utils::AocError;
+use crate::generators::Rng;
+use crate::profile::{Ctx, Profile};
+
+///answer of a part, numbers are kept apart from text so that "042" and 42 are the same answer when verifying
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Answer {
+    ///read an answer back from its text form (outputs, expected answers), numbers are recognized even with surrounding whitespaces
+    pub fn parse(text: &str) -> Self {
+        text.trim().parse().map(Answer::Number).unwrap_or_else(|_| Answer::Text(text.to_string()))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(t) => write!(f, "{t}"),
+        }
+    }
+}
+
+///anything displayable can be the result of a part (numbers, strings, grids of letters, ...)
+pub trait IntoAnswer {
+    fn into_answer(self) -> Answer;
+}
+
+impl <T: Display> IntoAnswer for T {
+    fn into_answer(self) -> Answer {
+        Answer::parse(self.to_string().as_str())
+    }
+}
+
+///one implementation of a part, several variants of the same part must always agree on the answer
+///the solution itself can return any IntoAnswer and any error convertible into AocError, advent_of_code_setup!() wraps it
+///run receives the raw input, the output of the day's parse function (or () if the day has no parse function) and the profiling context
+pub struct Variant {
+    pub name: &'static str,
+    pub run: fn(&str, &dyn Any, &Ctx) -> Result<Answer, AocError>,
+}
+
+///type erased parse function of a day, see Day
+pub type Parser = fn(&str) -> Result<Box<dyn Any>, AocError>;
+
+///animation of the input of a day in the terminal at the given frame rate, see Day
+pub type Watcher = fn(&str, f64) -> Result<(), AocError>;
+
+///all the variants of both parts of a day, generated by advent_of_code_setup!()
+///parse is the optional shared parser of the day, its output is type erased so it must not borrow the input
+///generate is the optional generator of synthetic inputs of the day (see generators)
+///watch is the optional animation of the day in the terminal, shown at most fps frames per second
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub parse: Option<Parser>,
+    pub generate: Option<fn(&mut Rng, usize) -> String>,
+    pub watch: Option<Watcher>,
+    pub a: &'static [Variant],
+    pub b: &'static [Variant],
+}
+
+pub struct PartReport {
+    pub answer: Answer,
+    pub timings: Vec<(&'static str, Duration)>,
+}
+
+impl PartReport {
+    ///timing of each variant, followed by how many times slower than the fastest variant it is (if there is more than one variant)
+    pub fn timings(&self) -> String {
+        let fastest = self.timings.iter().map(|(_, t)| *t).min().unwrap_or_default();
+        self.timings.iter().map(|(name, t)| {
+            if self.timings.len() > 1 {
+                format!("{name} {t:?} (x{:.2})", t.as_secs_f64() / fastest.as_secs_f64().max(f64::EPSILON))
+            } else {
+                format!("{name} {t:?}")
+            }
+        }).collect::<Vec<String>>().join(", ")
+    }
+}
+
+pub struct DayReport {
+    pub parse: Option<Duration>,
+    pub a: PartReport,
+    pub b: PartReport,
+    ///spans of the parse function and of each variant ({part}/{variant}), with the spans opened by the solutions nested inside
+    pub profile: Profile,
+}
+
+pub fn find_day(year: u32, day: u32) -> Option<&'static Day> {
+    crate::DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+impl Day {
+    ///run every variant of both parts, or only the selected variant (falling back to main for the parts that do not have it)
+    ///the input is parsed only once (if the day has a parse function) and shared by all the variants
+    pub fn run(&self, input: &str, variant: Option<&str>) -> Result<DayReport, String> {
+        self.check_variant(variant)?;
+        let ctx = Ctx::new();
+        let (parsed, parse) = {
+            let _s = ctx.span("parse");
+            self.parse_input(input)?
+        };
+        Ok(DayReport {
+            parse,
+            a: run_part("a", self.a, input, parsed.as_ref(), variant, &ctx).map_err(|e| format!("part a: {e}"))?,
+            b: run_part("b", self.b, input, parsed.as_ref(), variant, &ctx).map_err(|e| format!("part b: {e}"))?,
+            profile: ctx.profile(),
+        })
+    }
+
+    ///error if a variant is selected but neither part has it
+    pub(crate) fn check_variant(&self, variant: Option<&str>) -> Result<(), String> {
+        match variant {
+            Some(variant) if !self.a.iter().chain(self.b).any(|v| v.name == variant) => Err(format!("no variant named {variant}")),
+            _ => Ok(()),
+        }
+    }
+
+    ///output of the parse function of the day (or () if there is none) and how long it took
+    pub(crate) fn parse_input(&self, input: &str) -> Result<(Box<dyn Any>, Option<Duration>), String> {
+        if let Some(parse) = self.parse {
+            let start = Instant::now();
+            let parsed = parse(input).map_err(|e| format!("parse: {}", e.locate(input).render()))?;
+            Ok((parsed, Some(start.elapsed())))
+        } else {
+            Ok((Box::new(()), None))
+        }
+    }
+}
+
+///body of the tests generated by advent_of_code_setup!() for each example, every variant of the part must find the expected answer
+pub fn check_example(year: u32, day: u32, part: char, input: &str, expected: &str) {
+    let day = find_day(year, day).unwrap_or_else(|| panic!("{year}/12/{day} is not registered"));
+    let variants = if part == 'a' { day.a } else { day.b };
+    let report = day.parse_input(input).and_then(|(parsed, _)| run_part(&part.to_string(), variants, input, parsed.as_ref(), None, &Ctx::disabled())).unwrap_or_else(|e| panic!("{e}"));
+    assert_eq!(report.answer, Answer::parse(expected.trim_end_matches(['\n', '\r'])));
+}
+
+///variants of a part to run: all of them if none is selected, the selected one or main if the part does not have it
+pub(crate) fn selected_variants<'v>(variants: &'v [Variant], selected: Option<&str>) -> impl Iterator<Item = &'v Variant> {
+    let selected = match selected {
+        Some(s) if !variants.iter().any(|v| v.name == s) => Some("main"),
+        s => s,
+    };
+    variants.iter().filter(move |v| selected.is_none_or(|s| v.name == s))
+}
+
+fn run_part(part: &str, variants: &[Variant], input: &str, parsed: &dyn Any, selected: Option<&str>, ctx: &Ctx) -> Result<PartReport, String> {
+    let mut answer: Option<(&str, Answer)> = None;
+    let mut timings = Vec::new();
+    for variant in selected_variants(variants, selected) {
+        let span = ctx.span(format!("{part}/{}", variant.name).as_str());
+        let start = Instant::now();
+        let res = (variant.run)(input, parsed, ctx).map_err(|e| format!("{} variant failed: {}", variant.name, e.locate(input).render()))?;
+        timings.push((variant.name, start.elapsed()));
+        drop(span);
+        match &answer {
+            Some((name, first)) if *first != res => {
+                return Err(format!("variants disagree: {name} answered {first} but {} answered {res}", variant.name));
+            }
+            None => answer = Some((variant.name, res)),
+            _ => {}
+        }
+    }
+    answer.map(|(_, answer)| PartReport { answer, timings }).ok_or_else(|| "no variant to run".to_string())
+}
@@ -0,0 +1,42 @@
+use crate::advent_utils::AocError;
+
+//output of parse shared by both parts, replace the lines with whatever the puzzle needs
+pub struct Input {
+    pub lines: Vec<String>,
+}
+
+pub fn parse(input: &str) -> Result<Input, AocError> {
+    Ok(Input { lines: input.lines().map(|l| l.to_string()).collect() })
+}
+
+pub fn {{part_a}}(_input: &Input) -> Result<String, AocError> {
+    Ok("".to_string())
+}
+
+pub fn {{part_b}}(_input: &Input) -> Result<String, AocError> {
+    Ok("".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    //(input, expected a, expected b) of the examples of {{year}}/12/{{day}}, empty answers are not checked
+    //examples can also be stored in inputs/{{year}}/examples (see advent_of_code_setup!)
+    const EXAMPLES: [(&str, &str, &str); 1] = [
+        ("", "", ""),
+    ];
+
+    #[test]
+    fn examples() {
+        for (text, a, b) in EXAMPLES {
+            let input = parse(text).unwrap();
+            if !a.is_empty() {
+                assert_eq!({{part_a}}(&input).unwrap().to_string(), a);
+            }
+            if !b.is_empty() {
+                assert_eq!({{part_b}}(&input).unwrap().to_string(), b);
+            }
+        }
+    }
+}
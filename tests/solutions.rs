@@ -0,0 +1,11 @@
+use advent_of_code::functions::y2024::d1;
+use advent_of_code::runner::{find_day, Answer};
+
+#[test]
+fn test_solutions_are_reachable() {
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+    let parsed = d1::parse(input).unwrap();
+    assert_eq!(d1::y2024d1a(&parsed), Ok(11));
+    let report = find_day(2024, 1).unwrap().run(input, None).unwrap();
+    assert_eq!(report.b.answer, Answer::Number(31));
+}
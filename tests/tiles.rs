@@ -0,0 +1,17 @@
+use advent_of_code::advent_utils::{Grid, Tile};
+
+#[derive(Tile, Copy, Clone, Debug, Eq, PartialEq)]
+enum Cell {
+    #[tile('.')]
+    Empty,
+    #[tile('#')]
+    Wall,
+}
+
+#[test]
+fn test_tile_derive_outside_of_the_crate() {
+    let grid = Grid::parse_tiles(".#\n#.", Cell::Wall).unwrap();
+    assert_eq!((grid.get(0, 0), grid.get(1, 0)), (&Cell::Empty, &Cell::Wall));
+    assert_eq!(grid.to_string(), ".#\n#.");
+    assert_eq!(Cell::try_from('o').unwrap_err().message, "invalid Cell tile 'o'");
+}
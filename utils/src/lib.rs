@@ -3,7 +3,7 @@ use std::fs::OpenOptions;
 use std::io::Write;
 use chrono::prelude::*;
 
-pub const FIRST_YEAR: u32 = 2024; //true first year: 2015
+pub const FIRST_YEAR: u32 = 2024; //true first year: 2015, only used as the default first year of advent_of_code_setup!()
 
 pub fn get_year_month_day() -> (u32, u32, u32) {
     let date = Utc::now().with_timezone(&FixedOffset::west_opt(5 * 3600).unwrap());
@@ -16,28 +16,87 @@ pub fn get_advent_year_month_day() -> Option<(u32, u32, u32)> {
     if year < FIRST_YEAR { None } else { Some((year, month, _day)) }
 }
 
-pub fn setup_inputs_and_outputs(year: u32) {
+///number of days of an event: 25 until 2024, 12 since 2025
+pub fn advent_days(year: u32) -> u32 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+pub fn setup_inputs_and_outputs(year: u32, days: u32) {
     fs::create_dir_all(format!("inputs/{year}")).ok(); //since create_dir_all does not have a distinction between "already exists", "missing parent" and "invalid rights", we just ignore the error (the rest of the code will not run anyway if the directory was not created)
     fs::create_dir_all(format!("outputs/{year}")).ok(); //same as above
-    for d in 1..=25 {
-        OpenOptions::new().create(true).append(true).open(format!("inputs/{year}/{d}.txt")).expect(format!("Cannot create input for day {d} of year {year}").as_str());
+    for d in 1..=days {
+        OpenOptions::new().create(true).append(true).open(format!("inputs/{year}/{d}.txt")).unwrap_or_else(|_| panic!("Cannot create input for day {d} of year {year}"));
+    }
+}
+
+///template used to scaffold a day when no template file is found (a copy of templates/day.rs.tpl taken when utils is compiled), see fill_template for the placeholders
+pub const DEFAULT_TEMPLATE: &str = include_str!("../../templates/day.rs.tpl");
+
+///placeholders: {{year}}, {{day}}, {{part_a}} and {{part_b}} (names of the functions of each part, like y2024d6a)
+pub fn fill_template(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{{year}}", year.to_string().as_str())
+        .replace("{{day}}", day.to_string().as_str())
+        .replace("{{part_a}}", format!("y{year}d{day}a").as_str())
+        .replace("{{part_b}}", format!("y{year}d{day}b").as_str())
+}
+
+///true for names made of the prefix followed by a number (d12, y2024)
+fn is_numbered(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix).is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+///content of a mod.rs declaring the given modules, any line of the current content that is not the declaration of a module named prefix + number is kept after them
+///this way hand added modules (shared helpers, ...) survive the regeneration, but the modules that are no longer generated are removed
+pub fn mod_file_content(current: &str, modules: &[String], prefix: &str) -> String {
+    let mut content: String = modules.iter().map(|m| format!("pub mod {m};\n")).collect();
+    for line in current.lines() {
+        if !line.trim().strip_prefix("pub mod ").and_then(|l| l.strip_suffix(';')).is_some_and(|m| is_numbered(m, prefix)) {
+            content.push_str(line);
+            content.push('\n');
+        }
     }
+    content
 }
 
-pub fn setup_functions(year: u32) {
+///regenerate a mod.rs (see mod_file_content), the file is only written if its content changes so the build is not invalidated for nothing
+pub fn update_mod_file(path: &str, modules: &[String], prefix: &str) {
+    let current = fs::read_to_string(path).unwrap_or_default();
+    let content = mod_file_content(current.as_str(), modules, prefix);
+    if content != current {
+        fs::write(path, content).unwrap_or_else(|_| panic!("Cannot write mod file {path}"));
+    }
+}
+
+///scaffold the missing days of a year from the template, existing day files are never overwritten
+pub fn setup_functions(year: u32, days: u32, template: &str) {
     fs::create_dir_all(format!("src/functions/y{year}")).ok();
-    let mut m = OpenOptions::new().create(true).write(true).open(format!("src/functions/y{year}/mod.rs")).expect(format!("Cannot create mod file for year {year}").as_str());
-    for d in 1..=25 {
-        m.write_fmt(format_args!("pub mod d{d};\n")).expect(format!("Cannot write mod file for year {year}").as_str());
+    update_mod_file(format!("src/functions/y{year}/mod.rs").as_str(), &(1..=days).map(|d| format!("d{d}")).collect::<Vec<String>>(), "d");
+    for d in 1..=days {
         if let Ok(mut f) = OpenOptions::new().create_new(true).write(true).open(format!("src/functions/y{year}/d{d}.rs")) {
-            f.write_fmt(format_args!(
-                "pub fn y{year}d{d}a(_input: &str) -> Result<String, String> {{\
-                \n    Ok(\"\".to_string())\n\
-                }}\n\n\
-                pub fn y{year}d{d}b(_input: &str) -> Result<String, String> {{\
-                \n    Ok(\"\".to_string())\n\
-                }}\n"
-            )).expect(format!("Cannot write function file for day {d} of year {year}").as_str());
+            f.write_all(fill_template(template, year, d).as_bytes()).unwrap_or_else(|_| panic!("Cannot write function file for day {d} of year {year}"));
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_mod_file_content() {
+        let modules = vec!["d1".to_string(), "d2".to_string()];
+        assert_eq!(mod_file_content("", &modules, "d"), "pub mod d1;\npub mod d2;\n");
+        assert_eq!(mod_file_content("pub mod d1;\npub mod d2;\npub mod d3;\npub mod helpers;\n", &modules, "d"), "pub mod d1;\npub mod d2;\npub mod helpers;\n");
+        let content = mod_file_content("pub mod dijkstra;\n", &modules, "d");
+        assert_eq!(mod_file_content(content.as_str(), &modules, "d"), content);
+    }
+
+    #[test]
+    fn test_default_template() {
+        let day = fill_template(DEFAULT_TEMPLATE, 2024, 9);
+        assert!(day.contains("pub fn parse(") && day.contains("pub fn y2024d9a(") && day.contains("pub fn y2024d9b("));
+        assert!(day.contains("#[cfg(test)]") && day.contains("of 2024/12/9"));
+        assert!(!day.contains("{{"));
+    }
+}